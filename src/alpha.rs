@@ -1,22 +1,37 @@
-use crate::basics::{Core, CoreInterface};
+use crate::basics::{Core, CoreInterface, R};
 use crate::symbol::Symbol;
+use crate::unify::MetaVar;
+use std::cell::RefCell;
 
+/// Whether `e1` and `e2` are the same up to the names of bound variables. Metavariables are
+/// solved along the way, but only if the expressions turn out to be the same.
 pub fn is_alpha_equiv(e1: &Core, e2: &Core) -> bool {
-    alpha_equiv_aux(0, &Bindings::new(), &Bindings::new(), e1, e2)
+    let trail = Trail::default();
+    let root = Bindings::Nil(Some(&trail));
+    let equiv = alpha_equiv_aux(0, &root, &root, e1, e2);
+    if !equiv {
+        for meta in trail.into_inner() {
+            meta.unsolve();
+        }
+    }
+    equiv
 }
 
 pub fn alpha_equiv_aux(lvl: usize, b1: &Bindings, b2: &Bindings, e1: &Core, e2: &Core) -> bool {
     e1.alpha_equiv_aux(e2, lvl, b1, b2)
 }
 
+/// The metavariables solved while comparing two expressions
+pub type Trail = RefCell<Vec<R<MetaVar>>>;
+
 pub enum Bindings<'a> {
-    Nil,
+    Nil(Option<&'a Trail>),
     B(&'a Symbol, usize, &'a Bindings<'a>),
 }
 
 impl<'a> Bindings<'a> {
     pub fn new() -> Self {
-        Bindings::Nil
+        Bindings::Nil(None)
     }
 
     pub fn bind(&'a self, x: &'a Symbol, lvl: usize) -> Self {
@@ -25,9 +40,17 @@ impl<'a> Bindings<'a> {
 
     pub fn assv(&self, x: &Symbol) -> Option<(&'a Symbol, usize)> {
         match self {
-            Bindings::Nil => None,
+            Bindings::Nil(_) => None,
             Bindings::B(y, lvl, _) if x == *y => Some((y, *lvl)),
             Bindings::B(_, _, next) => next.assv(x),
         }
     }
+
+    /// Where the metavariables solved in the comparison are recorded, if anywhere
+    pub fn trail(&self) -> Option<&'a Trail> {
+        match self {
+            Bindings::Nil(trail) => *trail,
+            Bindings::B(_, _, next) => next.trail(),
+        }
+    }
 }
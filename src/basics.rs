@@ -4,7 +4,19 @@ use crate::fresh::freshen;
 use crate::normalize::val_in_ctx;
 use crate::sexpr::Sexpr;
 use crate::symbol::Symbol;
+pub use crate::syntax::SyntaxRule;
+use crate::types::data;
+pub use crate::types::data::{Constructor, DataDecl};
+use crate::types::functions::{check_with_implicit_lambda, ImplicitLambda, ImplicitPi};
+use crate::types::matching::Clause;
+use crate::types::meta::Meta;
+use crate::types::motive::Eliminator;
+use crate::types::record;
+pub use crate::types::record::RecordDecl;
 use crate::types::reference::NeutralVar;
+pub use crate::types::todo::Goal;
+use crate::types::todo::Holes;
+use crate::types::universe::universe_level_of;
 use crate::types::{cores, values};
 use sexpr_matcher::match_sexpr;
 use sexpr_parser::parse;
@@ -42,6 +54,11 @@ pub trait CoreInterface: Any + Debug + Display + Sync + Send {
     ) -> bool;
 
     fn resugar(&self) -> (HashSet<Symbol>, Core);
+
+    /// The expression itself, if it is wrapped in a `Core`
+    fn as_core(&self) -> Option<&Core> {
+        None
+    }
 }
 
 impl dyn CoreInterface {
@@ -117,13 +134,8 @@ impl Core {
         cores::quote(s)
     }
 
-    pub fn nat(mut x: u64) -> Self {
-        let mut n = cores::zero();
-        while x > 0 {
-            x -= 1;
-            n = Core::add1(n)
-        }
-        n
+    pub fn nat(x: u64) -> Self {
+        cores::the_nat(x)
     }
 
     pub fn add1(n: Core) -> Self {
@@ -157,6 +169,11 @@ impl CoreInterface for Core {
     }
 
     fn check(&self, ctx: &Ctx, r: &Renaming, tv: &Value) -> Result<Core> {
+        if tv.try_as::<ImplicitPi<Value, Closure>>().is_some()
+            && self.try_as::<ImplicitLambda>().is_none()
+        {
+            return check_with_implicit_lambda(ctx, r, self, tv);
+        }
         self.0.check(ctx, r, tv)
     }
 
@@ -167,12 +184,26 @@ impl CoreInterface for Core {
         b1: &alpha::Bindings,
         b2: &alpha::Bindings,
     ) -> bool {
+        // metavariables may occur on either side
+        if other.try_as::<Meta>().is_some() && self.try_as::<Meta>().is_none() {
+            return other.alpha_equiv_aux(self, lvl, b2, b1);
+        }
+        // records are the same as their layouts
+        if self.try_as::<Meta>().is_none() {
+            if let Some(other) = record::unfold(other) {
+                return self.alpha_equiv_aux(&other, lvl, b1, b2);
+            }
+        }
         self.0.alpha_equiv_aux(other, lvl, b1, b2)
     }
 
     fn resugar(&self) -> (HashSet<Symbol>, Core) {
         self.0.resugar()
     }
+
+    fn as_core(&self) -> Option<&Core> {
+        Some(self)
+    }
 }
 
 impl Display for Core {
@@ -185,7 +216,7 @@ impl FromStr for Core {
     type Err = String;
     fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
         let sexpr = parse::<Sexpr>(s).map_err(|e| format!("{:?}", e))?;
-        Ok((&sexpr).into())
+        Ok((&sexpr.name_holes()).into())
     }
 }
 
@@ -195,7 +226,8 @@ impl From<&Sexpr> for Core {
             sexpr,
             case [Sexpr::Invalid(s)] => cores::invalid_syntax(s),
             case ("the", t, v) => Core::the(Core::from(t), Core::from(v)),
-            case [Sexpr::SmallNat(x)] => cores::the_nat(*x),
+            case [Sexpr::Nat(x)] => cores::numeral(x.clone()),
+            case [Sexpr::Int(x)] => cores::int_literal(x.clone()),
             case "U" => cores::universe(),
             case "Nat" => cores::nat(),
             case "zero" => cores::zero(),
@@ -204,19 +236,20 @@ impl From<&Sexpr> for Core {
             case ("iter-Nat", target, base, step) => cores::iter_nat(Core::from(target), Core::from(base), Core::from(step)),
             case ("rec-Nat", target, base, step) => cores::rec_nat(Core::from(target), Core::from(base), Core::from(step)),
             case ("ind-Nat", target, motive, base, step) => cores::ind_nat(Core::from(target), Core::from(motive), Core::from(base), Core::from(step)),
+            case ("ind-Nat", target, base, step) => cores::inferred_motive(Eliminator::IndNat, vec![target.into(), base.into(), step.into()]),
             case ("Pi", [Sexpr::List(params)], rt) => Core::pi_star(parse_binders(params), Core::from(rt)),
             case ("Π", [Sexpr::List(params)], rt) => Core::pi_star(parse_binders(params), Core::from(rt)),
             case ("∏", [Sexpr::List(params)], rt) => Core::pi_star(parse_binders(params), Core::from(rt)),
+            case ("∀", [Sexpr::List(params)], rt) => parse_binders(params)
+                .into_iter()
+                .rev()
+                .fold(Core::from(rt), |t, (x, xt)| cores::implicit_pi(x, xt, t)),
+            case ("@", f, arg) => cores::implicit_app(f.into(), arg.into()),
             case ("->" :: [[ts@.., rt]]) => Core::fun(ts.iter().map(Core::from).collect(), Core::from(rt)),
-            case ("lambda", [Sexpr::List(params)], body) => cores::lambda_star(
-                        params.iter().map(|x| x.as_symbol().cloned().unwrap()).collect(),
-                        Core::from(body).into(),
-                    ),
-            case ("λ", [Sexpr::List(params)], body) => cores::lambda_star(
-                        params.iter().map(|x| x.as_symbol().cloned().unwrap()).collect(),
-                        Core::from(body).into(),
-                    ),
+            case ("lambda", [Sexpr::List(params)], body) => parse_lambda(params, body),
+            case ("λ", [Sexpr::List(params)], body) => parse_lambda(params, body),
             case "Atom" => cores::atom(),
+            case "Int" => cores::int(),
             case ("quote", [Sexpr::Symbol(s)]) => Core::quote(s.clone()),
             case ("Sigma", [Sexpr::List(params)], rt) => cores::sigma_star(parse_binders(params), Core::from(rt)),
             case ("Σ", [Sexpr::List(params)], rt) => cores::sigma_star(parse_binders(params), Core::from(rt)),
@@ -231,14 +264,18 @@ impl From<&Sexpr> for Core {
             case ("List", t) => cores::list(t.into()),
             case ("rec-List", target, base, step) => cores::rec_list(Core::from(target), Core::from(base), Core::from(step)),
             case ("ind-List", target, motive, base, step) => cores::ind_list(target.into(), motive.into(), base.into(), step.into()),
+            case ("ind-List", target, base, step) => cores::inferred_motive(Eliminator::IndList, vec![target.into(), base.into(), step.into()]),
             case "Absurd" => cores::absurd(),
             case ("ind-Absurd", target, motive) => cores::ind_absurd(target.into(), motive.into()),
             case ("=", t, from, to) => cores::equal(t.into(), from.into(), to.into()),
             case ("same", e) => cores::same(e.into()),
             case ("replace", t, m, b) => cores::replace(t.into(), m.into(), b.into()),
+            case ("replace", t, b) => cores::inferred_motive(Eliminator::Replace, vec![t.into(), b.into()]),
             //
             case ("cong", e, f) => cores::cong(e.into(), f.into()),
             case ("symm", e) => cores::symm(e.into()),
+            case ("trans", p1, p2) => cores::trans(p1.into(), p2.into()),
+            case ("ind-=", t, m, b) => cores::ind_eq(t.into(), m.into(), b.into()),
             //
             case ("Vec", t, n) => cores::vec(t.into(), n.into()),
             case "vecnil" => cores::vecnil(),
@@ -246,12 +283,46 @@ impl From<&Sexpr> for Core {
             case ("head", v) => cores::head(v.into()),
             case ("tail", v) => cores::tail(v.into()),
             case ("ind-Vec", n, target, motive, base, step) => cores::ind_vec(n.into(), target.into(), motive.into(), base.into(), step.into()),
+            case ("ind-Vec", n, target, base, step) => cores::inferred_motive(Eliminator::IndVec, vec![n.into(), target.into(), base.into(), step.into()]),
             case ("Either", l, r) => cores::either(l.into(), r.into()),
             case ("left", v) => cores::left(v.into()),
             case ("right", v) => cores::right(v.into()),
             case ("ind-Either", t, m, l, r) => cores::ind_either(t.into(), m.into(), l.into(), r.into()),
+            case ("ind-Either", t, l, r) => cores::inferred_motive(Eliminator::IndEither, vec![t.into(), l.into(), r.into()]),
+            case ("Enum" :: [labels]) => match labels.iter().map(parse_label).collect() {
+                    Some(labels) => cores::enumeration(labels),
+                    None => cores::invalid_syntax(&sexpr.to_string()),
+                },
+            case ("ind-Enum" :: [[target, motive, cases @ ..]]) => cores::ind_enum(target.into(), motive.into(), parse_sexpr_list(cases)),
+            case ("Stream", t) => cores::stream(t.into()),
+            case ("corec-Stream", seed, step) => cores::corec_stream(seed.into(), step.into()),
+            case ("succ-Int", i) => cores::succ_int(i.into()),
+            case ("pred-Int", i) => cores::pred_int(i.into()),
+            case ("negate-Int", i) => cores::negate_int(i.into()),
+            case ("ind-Int", t, m, b, u, d) => cores::ind_int(t.into(), m.into(), b.into(), u.into(), d.into()),
+            case ("atom=?", a, b) => cores::atom_eq(a.into(), b.into()),
+            case ("atom-apart", a, b) => match (parse_label(a), parse_label(b)) {
+                    (Some(a), Some(b)) => cores::atom_apart(a, b),
+                    _ => cores::invalid_syntax(&sexpr.to_string()),
+                },
+            case ("stream-head", s) => cores::stream_head(s.into()),
+            case ("stream-tail", s) => cores::stream_tail(s.into()),
+            case ("make", [Sexpr::Symbol(record)] :: [fields]) => cores::make(record.clone(), parse_binders(fields)),
+            case ("field", e, [Sexpr::Symbol(x)]) => cores::field(e.into(), x.clone()),
             case ("TODO", [Sexpr::Symbol(name)]) => cores::todo(name.clone()),
-            case [Sexpr::Symbol(s)] => if is_var_name(s) {
+            case "TODO" => cores::todo("TODO"),
+            case ("let", [Sexpr::List(bindings)], body) => cores::let_(parse_binders(bindings), body.into()),
+            case ("let*", [Sexpr::List(bindings)], body) => parse_binders(bindings)
+                .into_iter()
+                .rev()
+                .fold(body.into(), |body, binding| cores::let_(vec![binding], body)),
+            case ("match", target :: [clauses]) => match clauses.iter().map(parse_clause).collect() {
+                    Some(clauses) => cores::match_(target.into(), clauses),
+                    None => cores::invalid_syntax(&sexpr.to_string()),
+                },
+            case [Sexpr::Symbol(s)] => if let Some(level) = universe_level_of(s) {
+                    cores::universe_level(level)
+                } else if is_var_name(s) {
                     cores::refer(s.clone())
                 } else {
                     todo!("{:?}", s)
@@ -284,7 +355,58 @@ impl From<&Sexpr> for Symbol {
     }
 }
 
-fn parse_binders(exprs: &[Sexpr]) -> Vec<(Symbol, Core)> {
+/// A clause `(ctor body)` or `((ctor binder ...) body)`, if the expression is one
+fn parse_clause(sexpr: &Sexpr) -> Option<Clause> {
+    match_sexpr! {
+        sexpr,
+        case ([Sexpr::Symbol(ctor)], body) => Some(Clause {
+            ctor: ctor.clone(),
+            binders: vec![],
+            body: body.into(),
+        }),
+        case ([Sexpr::List(pattern)], body) => {
+            let names = pattern.iter().map(Sexpr::as_symbol).collect::<Option<Vec<_>>>()?;
+            let (ctor, binders) = names.split_first()?;
+            Some(Clause {
+                ctor: (*ctor).clone(),
+                binders: binders.iter().map(|&x| x.clone()).collect(),
+                body: body.into(),
+            })
+        },
+        else => None,
+    }
+}
+
+/// Parameters are plain names, or `(x A)` to give the argument's type
+fn parse_lambda(params: &[Sexpr], body: &Sexpr) -> Core {
+    if let Some(names) = params
+        .iter()
+        .map(Sexpr::as_symbol)
+        .collect::<Option<Vec<_>>>()
+    {
+        return cores::lambda_star(names.into_iter().cloned().collect(), body.into());
+    }
+    params
+        .iter()
+        .rev()
+        .fold(body.into(), |body, param| match param {
+            Sexpr::Symbol(x) => cores::lambda(x.clone(), body),
+            _ => {
+                let (x, t) = <(Symbol, Core)>::from(param);
+                cores::typed_lambda(x, t, body)
+            }
+        })
+}
+
+fn parse_label(sexpr: &Sexpr) -> Option<Symbol> {
+    match_sexpr!(
+        sexpr,
+        case ("quote", [Sexpr::Symbol(s)]) => Some(s.clone()),
+        else => None,
+    )
+}
+
+pub(crate) fn parse_binders(exprs: &[Sexpr]) -> Vec<(Symbol, Core)> {
     parse_sexpr_list(exprs)
 }
 
@@ -397,12 +519,17 @@ impl From<&Sexpr> for Value {
         match_sexpr! {
             sexpr,
             case [Sexpr::Invalid(s)] => panic!("invalid value: {}", s),
-            case [Sexpr::SmallNat(x)] => values::the_nat(*x),
+            case [Sexpr::Nat(x)] => values::numeral(x.clone()),
+            case [Sexpr::Int(x)] => values::int_literal(x.clone()),
             case "U" => values::universe(),
             case "Nat" => values::nat(),
             case "zero" => values::zero(),
             case "Atom" => values::atom(),
-            case [Sexpr::Symbol(s)] => panic!("invalid value: {:?}", s),
+            case "Int" => values::int(),
+            case [Sexpr::Symbol(s)] => match universe_level_of(s) {
+                Some(level) => values::universe_level(level),
+                None => panic!("invalid value: {:?}", s),
+            },
             case ("add1", n) => values::add1(Value::from(n)),
             case ("quote", [Sexpr::Symbol(s)]) => values::quote(s.clone()),
             case ("cons", car, cdr) => values::cons(car.into(), cdr.into()),
@@ -465,7 +592,7 @@ impl<T: NeutralInterface + 'static> From<T> for N {
 pub struct The(pub Value, pub Value);
 
 #[derive(Debug, Clone, PartialEq)]
-pub struct Ctx(R<CtxImpl>);
+pub struct Ctx(R<CtxImpl>, Holes);
 
 #[derive(Debug, PartialEq)]
 pub enum CtxImpl {
@@ -475,15 +602,35 @@ pub enum CtxImpl {
 
 impl Ctx {
     pub fn new() -> Self {
-        Ctx(R::new(CtxImpl::Nil))
+        Ctx(R::new(CtxImpl::Nil), Holes::default())
+    }
+
+    /// The same context, but one that collects the goals of the holes that are checked in it
+    /// and in the contexts made from it
+    pub fn collecting_goals(&self) -> Self {
+        Ctx(self.0.clone(), Holes::collecting())
+    }
+
+    /// The goals collected by this context, which stops collecting them
+    pub fn take_goals(&mut self) -> Vec<Goal> {
+        std::mem::take(&mut self.1).goals()
+    }
+
+    pub(crate) fn holes(&self) -> &Holes {
+        &self.1
     }
 
     pub fn claim(&self, name: impl Into<Symbol>, t: Core) -> Result<Self> {
         let name = name.into();
         match self.0.assv(&name) {
             Some((_, Binder::Claim(_))) => return Err(Error::ClaimedName(name)),
-            Some((_, Binder::Def(_, _))) => return Err(Error::DefinedName(name)),
-            Some((_, Binder::Free(_))) => unreachable!("claims are only allowed in the global context, and there should never be free variables"),
+            Some((
+                _,
+                Binder::Def(..) | Binder::Opaque(..) | Binder::Postulate(..) | Binder::Syntax(_),
+            )) => {
+                return Err(Error::DefinedName(name))
+            }
+            Some((_, Binder::Free(_) | Binder::Let(..))) => unreachable!("claims are only allowed in the global context, and there should never be free variables"),
             None => self.reclaim(name, t),
         }
     }
@@ -492,14 +639,109 @@ impl Ctx {
         let name = name.into();
         let tv = match self.0.assv(&name) {
             Some((_, Binder::Claim(tv))) => tv,
-            Some((_, Binder::Def(_, _))) => return Err(Error::DefinedName(name)),
-            Some((_, Binder::Free(_))) => unreachable!("definitions are only allowed in the global context, and there should never be free variables"),
+            Some((
+                _,
+                Binder::Def(..) | Binder::Opaque(..) | Binder::Postulate(..) | Binder::Syntax(_),
+            )) => {
+                return Err(Error::DefinedName(name))
+            }
+            Some((_, Binder::Free(_) | Binder::Let(..))) => unreachable!("definitions are only allowed in the global context, and there should never be free variables"),
             None => return Err(Error::UnclaimedName(name)),
         };
 
         let v_out = v.check(self, &Renaming::new(), &tv)?;
         let vv = val_in_ctx(self, &v_out);
-        Ok(self.extend(name, Binder::Def(tv.clone(), vv)))
+        let uses = self.uses(tv, &v_out)?;
+        Ok(self.extend(name, Binder::Def(tv.clone(), vv, uses, Some(v_out))))
+    }
+
+    /// Define a claimed name without exposing its value. The definition is checked once, and
+    /// afterwards the name is a constant that conversion never looks into.
+    pub fn define_opaque(&self, name: impl Into<Symbol>, v: Core) -> Result<Self> {
+        let name = name.into();
+        let tv = match self.0.assv(&name) {
+            Some((_, Binder::Claim(tv))) => tv,
+            Some((
+                _,
+                Binder::Def(..) | Binder::Opaque(..) | Binder::Postulate(..) | Binder::Syntax(_),
+            )) => {
+                return Err(Error::DefinedName(name))
+            }
+            Some((_, Binder::Free(_) | Binder::Let(..))) => unreachable!("definitions are only allowed in the global context, and there should never be free variables"),
+            None => return Err(Error::UnclaimedName(name)),
+        };
+
+        let v_out = v.check(self, &Renaming::new(), tv)?;
+        let uses = self.uses(tv, &v_out)?;
+        Ok(self.extend(name, Binder::Opaque(tv.clone(), uses)))
+    }
+
+    /// Assume a constant of type `t` without defining it
+    pub fn postulate(&self, name: impl Into<Symbol>, t: Core) -> Result<Self> {
+        let name = name.into();
+        match self.0.assv(&name) {
+            Some((_, Binder::Claim(_))) => return Err(Error::ClaimedName(name)),
+            Some((
+                _,
+                Binder::Def(..) | Binder::Opaque(..) | Binder::Postulate(..) | Binder::Syntax(_),
+            )) => {
+                return Err(Error::DefinedName(name))
+            }
+            Some((_, Binder::Free(_) | Binder::Let(..))) => unreachable!("postulates are only allowed in the global context, and there should never be free variables"),
+            None => {}
+        }
+
+        let t_out = t.is_type(self, &Renaming::new())?;
+        let tv = val_in_ctx(self, &t_out);
+        let uses = self.uses(&tv, &t_out)?;
+        Ok(self.extend(name, Binder::Postulate(tv, uses)))
+    }
+
+    /// The postulates that the definition of `name` depends on, directly or through other
+    /// definitions. A definition without any is free of axioms.
+    pub fn postulates_used_by(&self, name: &Symbol) -> Result<Vec<Symbol>> {
+        let mut found = vec![];
+        self.find_postulates(name, &mut HashSet::new(), &mut found)?;
+        found.sort_by(|a, b| a.name().cmp(b.name()));
+        Ok(found)
+    }
+
+    fn find_postulates(
+        &self,
+        x: &Symbol,
+        seen: &mut HashSet<*const CtxImpl>,
+        found: &mut Vec<Symbol>,
+    ) -> Result<()> {
+        let (binder, scope) = match self.0.lookup(x) {
+            Some(entry) => entry,
+            None => return Err(Error::UnknownVariable(x.clone())),
+        };
+        // a name may be redefined, so each entry is visited once rather than each name
+        if !seen.insert(R::as_ptr(&scope.0)) {
+            return Ok(());
+        }
+        let uses = match binder {
+            Binder::Postulate(_, uses) => {
+                found.push(x.clone());
+                uses
+            }
+            Binder::Def(_, _, uses, _) | Binder::Opaque(_, uses) => uses,
+            Binder::Claim(_) | Binder::Free(_) | Binder::Let(..) | Binder::Syntax(_) => {
+                return Ok(())
+            }
+        };
+        // the names used by a definition refer to the entries that were in scope for it
+        for y in uses {
+            scope.find_postulates(y, seen, found)?;
+        }
+        Ok(())
+    }
+
+    /// The global names that a definition's value `e` and its type `tv` refer to
+    fn uses(&self, tv: &Value, e: &Core) -> Result<HashSet<Symbol>> {
+        let globals = self.names_only();
+        let names = &e.occurring_names() | &tv.read_back_type(self)?.occurring_names();
+        Ok(&names & &globals)
     }
 
     pub fn reclaim(&self, name: impl Into<Symbol>, t: Core) -> Result<Self> {
@@ -518,7 +760,114 @@ impl Ctx {
 
         let v_out = v.check(self, &Renaming::new(), &tv)?;
         let vv = val_in_ctx(self, &v_out);
-        Ok(self.extend(name, Binder::Def(tv.clone(), vv)))
+        let uses = self.uses(tv, &v_out)?;
+        Ok(self.extend(name, Binder::Def(tv.clone(), vv, uses, Some(v_out))))
+    }
+
+    /// Define `name` again, with the hole `hole` in its definition filled in with `e`. The
+    /// expression is checked against the hole's type, in the hole's local context. The
+    /// definition is checked again where `name` was first defined, so `e` cannot refer to
+    /// `name` or to what was defined with it.
+    pub fn refine(&self, name: impl Into<Symbol>, hole: &Symbol, e: Core) -> Result<Self> {
+        let name = name.into();
+        let (tv, def, mut scope) = match self.0.lookup(&name) {
+            Some((Binder::Def(tv, _, _, Some(def)), scope)) => (tv.clone(), def.clone(), scope),
+            Some(_) => return Err(Error::NotADefinition(name)),
+            None => return Err(Error::UnknownVariable(name)),
+        };
+        while let Some((_, earlier)) = scope.0.lookup(&name) {
+            scope = earlier;
+        }
+
+        let scope = Ctx(scope.0.clone(), self.1.filling(hole.clone(), e));
+        let refined = def.check(&scope, &Renaming::new(), &tv);
+        if !scope.1.filled() {
+            return Err(Error::NoSuchHole(hole.clone(), name));
+        }
+        let v_out = refined?;
+        let vv = val_in_ctx(&scope, &v_out);
+        let uses = scope.uses(&tv, &v_out)?;
+        Ok(self.extend(name, Binder::Def(tv, vv, uses, Some(v_out))))
+    }
+
+    /// Refine the hole `hole` in the definition of `name` by splitting it into the cases of
+    /// the variable `x`, each with a new hole
+    pub fn split(&self, name: impl Into<Symbol>, hole: &Symbol, x: Symbol) -> Result<Self> {
+        self.refine(name, hole, cores::case_split(x, hole.clone()))
+    }
+
+    /// Refine the hole `hole` in the definition of `name` with a term found by searching
+    /// no deeper than `depth`
+    pub fn auto(&self, name: impl Into<Symbol>, hole: &Symbol, depth: usize) -> Result<Self> {
+        self.refine(name, hole, cores::search(depth))
+    }
+
+    pub fn data(&self, decl: DataDecl) -> Result<Self> {
+        data::declare(self, decl)
+    }
+
+    pub fn record(&self, decl: RecordDecl) -> Result<Self> {
+        record::declare(self, decl)
+    }
+
+    pub fn define_syntax(&self, name: impl Into<Symbol>, rule: SyntaxRule) -> Result<Self> {
+        let name = name.into();
+        if !is_var_name(&name) {
+            return Err(Error::NotAVarName(name));
+        }
+        match self.0.assv(&name) {
+            Some((_, Binder::Claim(_))) => Err(Error::ClaimedName(name)),
+            Some(_) => Err(Error::DefinedName(name)),
+            None => Ok(self.extend(name, Binder::Syntax(R::new(rule)))),
+        }
+    }
+
+    /// The macro called `name`, if there is one
+    pub(crate) fn syntax_rule(&self, name: &Symbol) -> Option<R<SyntaxRule>> {
+        match self.0.assv(name) {
+            Some((_, Binder::Syntax(rule))) => Some(rule.clone()),
+            _ => None,
+        }
+    }
+
+    /// All the macros in scope
+    pub(crate) fn syntax_rules(&self) -> Vec<R<SyntaxRule>> {
+        let mut rules = vec![];
+        let mut ctx = self;
+        while let CtxImpl::Entry(_, b, next) = &*ctx.0 {
+            if let Binder::Syntax(rule) = b {
+                rules.push(rule.clone());
+            }
+            ctx = next;
+        }
+        rules
+    }
+
+    /// Claim and define `name` in one go, with a value that is constructed directly from
+    /// the type's value rather than by evaluating an expression.
+    pub(crate) fn define_primitive(
+        &self,
+        name: impl Into<Symbol>,
+        t: Core,
+        make_value: impl FnOnce(Value) -> Value,
+    ) -> Result<Self> {
+        let name = name.into();
+        match self.0.assv(&name) {
+            Some((_, Binder::Claim(_))) => return Err(Error::ClaimedName(name)),
+            Some((
+                _,
+                Binder::Def(..) | Binder::Opaque(..) | Binder::Postulate(..) | Binder::Syntax(_),
+            )) => {
+                return Err(Error::DefinedName(name))
+            }
+            Some((_, Binder::Free(_) | Binder::Let(..))) => unreachable!("primitives are only allowed in the global context, and there should never be free variables"),
+            None => {}
+        }
+
+        let t_out = t.is_type(self, &Renaming::new())?;
+        let tv = val_in_ctx(self, &t_out);
+        let v = make_value(tv.clone());
+        Ok(self.extend(name, Binder::Def(tv, v, HashSet::new(), None)))
     }
 
     pub fn fresh(&self, x: &Symbol) -> Symbol {
@@ -537,8 +886,20 @@ impl Ctx {
         }
     }
 
+    /// Bind a local name to a known value, such as a `let`-bound expression
+    pub fn bind_val(&self, x: Symbol, tv: Value, v: Value) -> Result<Self> {
+        if self.0.assv(&x).is_some() {
+            Err(Error::AlreadyBound(x.clone(), self.clone()))
+        } else {
+            Ok(self.extend(x, Binder::Let(tv, v)))
+        }
+    }
+
     fn extend(&self, name: impl Into<Symbol>, binder: Binder) -> Self {
-        Ctx(R::new(CtxImpl::Entry(name.into(), binder, self.clone())))
+        Ctx(
+            R::new(CtxImpl::Entry(name.into(), binder, self.clone())),
+            self.1.clone(),
+        )
     }
 
     pub fn names_only(&self) -> HashSet<Symbol> {
@@ -552,10 +913,38 @@ impl Ctx {
         }
     }
 
+    /// The variables bound by binders such as λ, Π and `let`, outermost first, with their
+    /// types
+    pub fn local_variables(&self) -> Result<Vec<(Symbol, Core)>> {
+        match &*self.0 {
+            CtxImpl::Nil => Ok(vec![]),
+            CtxImpl::Entry(x, Binder::Free(tv) | Binder::Let(tv, _), next) => {
+                let mut vars = next.local_variables()?;
+                vars.push((x.clone(), tv.read_back_type(next)?));
+                Ok(vars)
+            }
+            CtxImpl::Entry(_, _, next) => next.local_variables(),
+        }
+    }
+
+    /// The names that an expression may refer to, innermost first, with their types
+    pub(crate) fn typed_names(&self) -> Vec<(Symbol, Value)> {
+        let mut names = vec![];
+        let mut seen = HashSet::new();
+        let mut ctx = self;
+        while let CtxImpl::Entry(x, b, next) = &*ctx.0 {
+            if !matches!(b, Binder::Claim(_) | Binder::Syntax(_)) && seen.insert(x.clone()) {
+                names.push((x.clone(), b.get_type()));
+            }
+            ctx = next;
+        }
+        names
+    }
+
     pub fn var_type(&self, x: &Symbol) -> Result<Value> {
         match &*self.0 {
             CtxImpl::Nil => Err(Error::UnknownVariable(x.clone())),
-            CtxImpl::Entry(_, Binder::Claim(_), next) => next.var_type(x),
+            CtxImpl::Entry(_, Binder::Claim(_) | Binder::Syntax(_), next) => next.var_type(x),
             CtxImpl::Entry(y, b, _) if x == y => Ok(b.get_type()),
             CtxImpl::Entry(_, _, next) => next.var_type(x),
         }
@@ -564,13 +953,17 @@ impl Ctx {
     pub fn to_env(&self) -> Env {
         match &*self.0 {
             CtxImpl::Nil => Env::new(),
-            CtxImpl::Entry(x, Binder::Def(_, v), next) => {
+            CtxImpl::Entry(x, Binder::Def(_, v, _, _) | Binder::Let(_, v), next) => {
                 let ctx = next;
                 let mut env = ctx.to_env();
                 env.0.insert(x.clone(), v.clone());
                 env
             }
-            CtxImpl::Entry(x, Binder::Free(tv), next) => {
+            CtxImpl::Entry(
+                x,
+                Binder::Free(tv) | Binder::Opaque(tv, _) | Binder::Postulate(tv, _),
+                next,
+            ) => {
                 let ctx = next;
                 let mut env = ctx.to_env();
                 env.0.insert(
@@ -579,7 +972,7 @@ impl Ctx {
                 );
                 env
             }
-            CtxImpl::Entry(_, Binder::Claim(_), next) => next.to_env(),
+            CtxImpl::Entry(_, Binder::Claim(_) | Binder::Syntax(_), next) => next.to_env(),
         }
     }
 }
@@ -593,6 +986,16 @@ impl CtxImpl {
         }
     }
 
+    /// The binder of `x`, other than a claim, and the context it was added to
+    fn lookup(&self, x: &Symbol) -> Option<(&Binder, &Ctx)> {
+        match self {
+            CtxImpl::Nil => None,
+            CtxImpl::Entry(_, Binder::Claim(_), next) => next.0.lookup(x),
+            CtxImpl::Entry(s, b, next) if s == x => Some((b, next)),
+            CtxImpl::Entry(_, _, next) => next.0.lookup(x),
+        }
+    }
+
     fn find_claim(&self, x: &Symbol) -> Option<&Value> {
         match self {
             CtxImpl::Nil => None,
@@ -605,14 +1008,30 @@ impl CtxImpl {
 #[derive(Debug, PartialEq)]
 pub enum Binder {
     Claim(Value),
-    Def(Value, Value),
+    /// A definition, with the global names it uses and the checked expression it was
+    /// defined as, if any
+    Def(Value, Value, HashSet<Symbol>, Option<Core>),
     Free(Value),
+    /// A local name bound to a known value, such as by `let`
+    Let(Value, Value),
+    /// A definition whose value is hidden
+    Opaque(Value, HashSet<Symbol>),
+    /// A constant that is assumed rather than defined
+    Postulate(Value, HashSet<Symbol>),
+    /// A macro, which is expanded away before type checking
+    Syntax(R<SyntaxRule>),
 }
 
 impl Binder {
     pub fn get_type(&self) -> Value {
         match self {
-            Binder::Claim(tv) | Binder::Def(tv, _) | Binder::Free(tv) => tv.clone(),
+            Binder::Claim(tv)
+            | Binder::Def(tv, _, _, _)
+            | Binder::Free(tv)
+            | Binder::Let(tv, _)
+            | Binder::Opaque(tv, _)
+            | Binder::Postulate(tv, _) => tv.clone(),
+            Binder::Syntax(_) => unreachable!("macros have no type"),
         }
     }
 }
@@ -639,6 +1058,7 @@ impl Env {
     }
 }
 
+#[derive(Clone)]
 pub struct Renaming {
     map: HashMap<Symbol, Symbol>,
 }
@@ -671,7 +1091,10 @@ pub fn is_var_name(x: &str) -> bool {
         | "cons" | "car" | "cdr" | "Trivial" | "sole" | "::" | "nil" | "List" | "rec-List"
         | "ind-List" | "Absurd" | "ind-Absurd" | "=" | "same" | "replace" | "symm" | "trans"
         | "cong" | "ind-=" | "Vec" | "vec::" | "vecnil" | "head" | "tail" | "ind-Vec"
-        | "Either" | "left" | "right" | "ind-Either" | "the" | "TODO" => false,
-        _ => true,
+        | "Either" | "left" | "right" | "ind-Either" | "the" | "TODO" | "match" | "let"
+        | "let*" | "∀" | "@" | "Enum" | "ind-Enum" | "Stream" | "corec-Stream" | "stream-head"
+        | "stream-tail" | "make" | "field" | "atom=?" | "atom-apart" | "Int" | "succ-Int"
+        | "pred-Int" | "negate-Int" | "ind-Int" => false,
+        _ => universe_level_of(x).is_none(),
     }
 }
@@ -189,3 +189,51 @@ fn frame_22_67_prove_that_twice_equals_double_then_use_that_to_define_twicevec()
         )
         .unwrap();
 }
+
+#[test]
+fn trans_chains_two_equalities() {
+    with_chapter_context()
+        .core("(trans (the (= Nat 2 (+ 1 1)) (same 2)) (the (= Nat (+ 1 1) (incr 1)) (same 2)))")
+        .is_a("(= Nat 2 (incr 1))")
+        .assert(true);
+
+    with_chapter_context()
+        .core("(trans (the (= Nat 2 (+ 1 1)) (same 2)) (the (= Nat (+ 1 1) (incr 1)) (same 2)))")
+        .and("(same 2)")
+        .are_the_same("(= Nat 2 2)")
+        .assert(true);
+}
+
+#[test]
+fn trans_requires_matching_middles() {
+    with_chapter_context()
+        .core("(trans (the (= Nat 2 2) (same 2)) (the (= Nat 3 3) (same 3)))")
+        .is_not_a("(= Nat 2 3)")
+        .assert(true);
+}
+
+#[test]
+fn trans_of_neutral_proofs() {
+    let ctx = with_chapter_context()
+        .claim(
+            "trans-Nat",
+            "(Π ((a Nat) (b Nat) (c Nat)) (-> (= Nat a b) (= Nat b c) (= Nat a c)))",
+        )
+        .define("trans-Nat", "(λ (a b c p q) (trans p q))")
+        .unwrap();
+
+    ctx.core("trans-Nat")
+        .and("(λ (a b c p q) (trans p q))")
+        .are_the_same("(Π ((a Nat) (b Nat) (c Nat)) (-> (= Nat a b) (= Nat b c) (= Nat a c)))")
+        .assert(true);
+
+    ctx.core("(λ (a b p) (trans-Nat a b b p (same b)))")
+        .and("(λ (a b p) (trans p (the (= Nat b b) (same b))))")
+        .are_the_same("(Π ((a Nat) (b Nat)) (-> (= Nat a b) (= Nat a b)))")
+        .assert(true);
+
+    ctx.core("(trans-Nat 4 4 4 (same 4) (same 4))")
+        .and("(same 4)")
+        .are_the_same("(= Nat 4 4)")
+        .assert(true);
+}
@@ -1,5 +1,7 @@
 use crate::book::common_definitions::with_book_context;
-use crate::book::{chapter_09, with_empty_context, Checker, Result, ResultBoolAssertions};
+use crate::book::{
+    chapter_09, with_empty_context, Checker, Result, ResultAssertions, ResultBoolAssertions,
+};
 use lazy_static::lazy_static;
 
 lazy_static! {
@@ -132,3 +134,41 @@ fn frame_77_specific_claim_with_correct_impl() {
         )
         .unwrap();
 }
+
+#[test]
+fn ind_eq_is_the_full_eliminator_for_equality() {
+    let ctx = with_chapter_context()
+        .claim(
+            "symm-via-ind-=",
+            "(Π ((A U) (from A) (to A)) (-> (= A from to) (= A to from)))",
+        )
+        .define(
+            "symm-via-ind-=",
+            "(λ (A from to p) (ind-= p (λ (x q) (= A x from)) (same from)))",
+        )
+        .unwrap();
+
+    ctx.core("(symm-via-ind-= Nat 3 3 (same 3))")
+        .and("(same 3)")
+        .are_the_same("(= Nat 3 3)")
+        .assert(true);
+
+    ctx.core("symm-via-ind-=")
+        .and("(λ (A from to p) (ind-= p (λ (x q) (= A x from)) (same from)))")
+        .are_the_same("(Π ((A U) (from A) (to A)) (-> (= A from to) (= A to from)))")
+        .assert(true);
+}
+
+#[test]
+fn ind_eq_base_must_match_the_motive() {
+    with_chapter_context()
+        .claim(
+            "bad-symm",
+            "(Π ((A U) (from A) (to A)) (-> (= A from to) (= A to from)))",
+        )
+        .define(
+            "bad-symm",
+            "(λ (A from to p) (ind-= p (λ (x q) (= A x from)) (same to)))",
+        )
+        .assert_err();
+}
@@ -12,12 +12,14 @@ pub enum Error {
     UnclaimedName(Symbol),
     DefinedName(Symbol),
     UnknownVariable(Symbol),
+    NotADefinition(Symbol),
+    NoSuchHole(Symbol, Symbol),
+    NotAVarName(Symbol),
 
     CantDetermineType(Core),
     InvalidAtom(Symbol),
     WrongType(Core, Core),
     AlreadyBound(Symbol, Ctx),
-    UhasNoType,
     NotAType(Core),
     NotAFunctionType(Core),
     NotAFunction(Core),
@@ -26,10 +28,33 @@ pub enum Error {
     NotAVecType(Core),
     NotAnEqualType(Core),
     NotAnEitherType(Core),
+    NotAnEnumType(Core),
+    NotAStreamType(Core),
+    NotARecordType(Core),
+    NotALabelOf(Symbol, Core),
+    DuplicateLabel(Symbol),
+    NotAFieldOf(Symbol, Core),
+    MissingField(Symbol),
+    DuplicateField(Symbol),
+    DuplicateParameter(Symbol),
     NotTheSame(Core, Core, Core),
+    AtomsNotApart(Symbol),
     WrongArity(Core),
     LengthNotZero(Core),
     LengthZero(Core),
+    InvalidConstructorType(Symbol, Core),
+    CantMatchOn(Core),
+    NotALambda(Core),
+    NotStructurallyRecursive(Core),
+    NotInStructuralPosition(Core),
+    NotAConstructorOf(Symbol, Core),
+    MissingClause(Symbol),
+    DuplicateClause(Symbol),
+    WrongPatternArity(Symbol, usize),
+    InvalidRecursiveOccurrence(Symbol, Core),
+    NotAnImplicitFunctionType(Core),
+    UnsolvedMeta(Symbol, Core),
+    NoProofFound(Core),
 
     TypeMismatchVar(Value, Value),
     NotATypeVar(Value),
@@ -50,7 +75,6 @@ impl Display for Error {
             Error::AlreadyBound(s, ctx) => {
                 write!(f, "Name {} is already bound in context {:?}", s.name(), ctx)
             }
-            Error::UhasNoType => write!(f, "U is a type, but does not have a type."),
             Error::NotAType(t) => write!(f, "Not a type: {}", t),
             Error::NotAFunctionType(t) => write!(f, "Not a function type: {}", t),
             Error::NotAFunction(e) => write!(f, "Not a function type: {}", e),
@@ -59,15 +83,71 @@ impl Display for Error {
             Error::NotAVecType(t) => write!(f, "Not a vec type: {}", t),
             Error::NotAnEqualType(t) => write!(f, "Not a = type: {}", t),
             Error::NotAnEitherType(t) => write!(f, "Not a Either type: {}", t),
+            Error::NotAnEnumType(t) => write!(f, "Not an Enum type: {}", t),
+            Error::NotAStreamType(t) => write!(f, "Not a Stream type: {}", t),
+            Error::NotALabelOf(x, t) => write!(f, "'{} is not a label of {}", x.name(), t),
+            Error::DuplicateLabel(x) => write!(f, "Label '{} occurs more than once", x.name()),
+            Error::NotARecordType(t) => write!(f, "Not a record type: {}", t),
+            Error::NotAFieldOf(x, t) => write!(f, "{} is not a field of {}", x.name(), t),
+            Error::MissingField(x) => write!(f, "Missing a value for the field {}", x.name()),
+            Error::DuplicateField(x) => write!(f, "Field {} occurs more than once", x.name()),
+            Error::DuplicateParameter(x) => {
+                write!(f, "Parameter {} occurs more than once", x.name())
+            }
+            Error::NotADefinition(name) => {
+                write!(f, "{} is not defined as an expression", name.name())
+            }
+            Error::NoSuchHole(hole, name) => {
+                write!(f, "There is no hole {} in {}", hole.name(), name.name())
+            }
             Error::UnknownVariable(name) => write!(f, "Unknown variable {}", name.name()),
+            Error::NotAVarName(name) => write!(f, "Not a valid name: {}", name.name()),
             Error::NotTheSame(t, a, b) => {
                 write!(f, "The expressions {} and {} are not the same {}", a, b, t)
             }
+            Error::AtomsNotApart(x) => {
+                write!(f, "The atoms '{} and '{} are the same", x.name(), x.name())
+            }
             Error::WrongArity(expr) => {
                 write!(f, "Wrong number of arguments: {}", expr)
             }
             Error::LengthNotZero(n) => write!(f, "Length must be zero but was {}", n),
             Error::LengthZero(n) => write!(f, "Length must be nonzero but was {}", n),
+            Error::NotALambda(e) => write!(f, "Expected a λ expression, got {}", e),
+            Error::NotStructurallyRecursive(e) => {
+                write!(f, "Recursive call is not structural: {}", e)
+            }
+            Error::NotInStructuralPosition(e) => {
+                write!(f, "Recursive call is not in structural position: {}", e)
+            }
+            Error::NotAnImplicitFunctionType(t) => {
+                write!(f, "Not a function type with an implicit argument: {}", t)
+            }
+            Error::UnsolvedMeta(x, e) => {
+                write!(f, "Can't infer the implicit argument {} in {}", x.name(), e)
+            }
+            Error::NoProofFound(t) => write!(f, "No proof found for {}", t),
+            Error::CantMatchOn(t) => write!(f, "Can't match on a target of type {}", t),
+            Error::NotAConstructorOf(c, t) => {
+                write!(f, "{} is not a constructor of {}", c.name(), t)
+            }
+            Error::MissingClause(c) => write!(f, "Missing a clause for {}", c.name()),
+            Error::DuplicateClause(c) => write!(f, "More than one clause for {}", c.name()),
+            Error::WrongPatternArity(c, n) => {
+                write!(f, "Pattern for {} must bind {} fields", c.name(), n)
+            }
+            Error::InvalidConstructorType(c, t) => write!(
+                f,
+                "Constructor {} must return its type applied to the parameters, but returns {}",
+                c.name(),
+                t
+            ),
+            Error::InvalidRecursiveOccurrence(name, t) => write!(
+                f,
+                "{} may only occur as the type of a constructor argument, but occurs in {}",
+                name.name(),
+                t
+            ),
             Error::TypeMismatchVar(v, t) => {
                 write!(f, "The value {:?} is not a {:?}", v, t)
             }
@@ -3,12 +3,16 @@ pub mod basics;
 pub mod errors;
 mod fresh;
 pub mod normalize;
+mod recursion;
 pub mod rep;
 pub mod resugar;
+mod section;
 pub mod sexpr;
 pub mod symbol;
+mod syntax;
 pub mod typechecker;
 mod types;
+mod unify;
 
 #[cfg(test)]
 pub mod book;
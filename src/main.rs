@@ -3,25 +3,94 @@ use the_little_typer as tlt;
 use std::{io, io::Write};
 use the_little_typer::rep;
 use tlt::{
-    basics::{Core, Ctx},
+    basics::{Core, Ctx, Goal},
     resugar::resugar,
 };
 
 fn main() -> io::Result<()> {
     let mut ctx = Ctx::new();
     prelude(&mut ctx);
-    loop {
-        match read_eval_normalize(&mut ctx) {
-            Ok(None) => {}
-            Ok(Some(out)) => println!("{}", resugar(&out)),
+
+    if let Some(path) = std::env::args().nth(1) {
+        let src = std::fs::read_to_string(path)?;
+        match top_level_forms(&src) {
+            Ok(forms) => {
+                for form in forms {
+                    print_result(rep::eval_with_goals(&mut ctx, &form));
+                }
+            }
             Err(e) => eprintln!("{}", e),
         }
+        return Ok(());
+    }
+
+    loop {
+        print_result(read_eval_normalize(&mut ctx));
+    }
+}
+
+fn print_result((result, goals): (Result<Option<Core>, String>, Vec<Goal>)) {
+    for goal in goals {
+        eprintln!("{}\n", goal);
+    }
+    match result {
+        Ok(None) => {}
+        Ok(Some(out)) => println!("{}", resugar(&out)),
+        Err(e) => eprintln!("{}", e),
+    }
+}
+
+/// Split the contents of a file into its top-level forms, which are lists or atoms, leaving
+/// out `;` comments
+fn top_level_forms(src: &str) -> Result<Vec<String>, String> {
+    let mut forms = vec![];
+    let mut form = String::new();
+    let mut depth = 0;
+    let mut start_line = 0;
+    for (line, text) in src.lines().enumerate().map(|(i, text)| (i + 1, text)) {
+        let code = text.split(';').next().unwrap_or_default();
+        for c in code.chars() {
+            // an atom at the top level ends where a list or a space begins
+            if depth == 0 && (c == '(' || c.is_whitespace()) && !form.is_empty() {
+                forms.push(std::mem::take(&mut form));
+            }
+            match c {
+                '(' => {
+                    if depth == 0 {
+                        start_line = line;
+                    }
+                    depth += 1;
+                    form.push(c);
+                }
+                ')' if depth == 0 => return Err(format!("Unbalanced ) on line {}", line)),
+                ')' => {
+                    depth -= 1;
+                    form.push(c);
+                    if depth == 0 {
+                        forms.push(std::mem::take(&mut form));
+                    }
+                }
+                _ if depth == 0 && c.is_whitespace() => {}
+                _ => form.push(c),
+            }
+        }
+        if depth > 0 {
+            form.push('\n');
+        } else if !form.is_empty() {
+            forms.push(std::mem::take(&mut form));
+        }
+    }
+    if depth > 0 {
+        return Err(format!("Unbalanced ( on line {}", start_line));
     }
+    Ok(forms)
 }
 
-fn read_eval_normalize(ctx: &mut Ctx) -> Result<Option<Core>, String> {
-    let src = read_line().map_err(|e| e.to_string())?;
-    rep::eval_normalize(ctx, &src)
+fn read_eval_normalize(ctx: &mut Ctx) -> (Result<Option<Core>, String>, Vec<Goal>) {
+    match read_line() {
+        Ok(src) => rep::eval_with_goals(ctx, &src),
+        Err(e) => (Err(e.to_string()), vec![]),
+    }
 }
 
 fn read_line() -> io::Result<String> {
@@ -72,3 +141,50 @@ fn prelude(ctx: &mut Ctx) {
         rep::eval_normalize(ctx, stmt).unwrap();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::top_level_forms;
+
+    #[test]
+    fn forms_are_split_around_comments() {
+        let src = "; (claim x\n(claim n ; a comment with a ) in it\n  Nat)\n(define n 1) ; (\n";
+        assert_eq!(
+            top_level_forms(src),
+            Ok(vec![
+                "(claim n \n  Nat)".to_string(),
+                "(define n 1)".to_string()
+            ])
+        );
+    }
+
+    #[test]
+    fn atoms_are_forms_too() {
+        assert_eq!(
+            top_level_forms("(claim n Nat)\nn 'a ; n\nzero(add1 n)"),
+            Ok(vec![
+                "(claim n Nat)".to_string(),
+                "n".to_string(),
+                "'a".to_string(),
+                "zero".to_string(),
+                "(add1 n)".to_string()
+            ])
+        );
+    }
+
+    #[test]
+    fn unbalanced_parentheses_are_reported() {
+        assert_eq!(
+            top_level_forms("(claim n Nat))"),
+            Err("Unbalanced ) on line 1".to_string())
+        );
+        assert_eq!(
+            top_level_forms("(claim n Nat)\n(define n\n  1"),
+            Err("Unbalanced ( on line 2".to_string())
+        );
+        assert_eq!(
+            top_level_forms("(claim n Nat)\nn)"),
+            Err("Unbalanced ) on line 2".to_string())
+        );
+    }
+}
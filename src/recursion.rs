@@ -0,0 +1,488 @@
+//! Structurally recursive definitions.
+//!
+//! `(define-rec f (λ (x ...) body))` may call `f` inside a `match` on one of its parameters,
+//! as long as the call passes a recursive field of the matched constructor in place of that
+//! parameter and all other parameters unchanged. Each such call is replaced with the clause's
+//! inductive hypothesis, so the definition becomes an ordinary `match`, and thus an `ind-*`
+//! eliminator, before it reaches the type checker.
+//!
+//! The hypothesis is the result of the `match` itself on the smaller value, so it only stands
+//! for the recursive call when the `match` is in tail position: the whole body of the λ, or
+//! the whole body of a clause of such a `match`.
+
+use crate::basics::Core;
+use crate::errors::{Error, Result};
+use crate::fresh::freshen;
+use crate::sexpr::Sexpr;
+use crate::symbol::Symbol;
+use std::collections::{HashMap, HashSet};
+
+/// Rewrite the recursive calls in the definition of `f` into inductive hypotheses
+pub fn desugar_rec(f: &Symbol, def: &Sexpr) -> Result<Sexpr> {
+    let (lambda, params, body) = match def {
+        Sexpr::List(items) => match &items[..] {
+            [lambda @ Sexpr::Symbol(l), Sexpr::List(params), body]
+                if l == &"λ" || l == &"lambda" =>
+            {
+                (lambda, params, body)
+            }
+            _ => return Err(Error::NotALambda(Core::from(def))),
+        },
+        _ => return Err(Error::NotALambda(Core::from(def))),
+    };
+
+    let param_names = params
+        .iter()
+        .map(|p| p.as_symbol().cloned())
+        .collect::<Option<Vec<_>>>()
+        .ok_or_else(|| Error::NotALambda(Core::from(def)))?;
+
+    if param_names.contains(f) {
+        return Ok(def.clone());
+    }
+
+    let mut rec = Recursion {
+        f: f.clone(),
+        params: param_names.iter().cloned().map(Some).collect(),
+        hypotheses: HashMap::new(),
+        used: symbols(def),
+    };
+
+    let body = rec.walk(body, true)?;
+    Ok(Sexpr::List(vec![
+        lambda.clone(),
+        Sexpr::List(params.clone()),
+        body,
+    ]))
+}
+
+struct Recursion {
+    f: Symbol,
+    /// The definition's parameters; `None` where a parameter is shadowed
+    params: Vec<Option<Symbol>>,
+    /// Recursive fields in scope, with the parameter they replace and their inductive
+    /// hypothesis, which they only have when their `match` is in tail position
+    hypotheses: HashMap<Symbol, (usize, Option<Symbol>)>,
+    used: HashSet<Symbol>,
+}
+
+impl Recursion {
+    /// Walk `expr`; it is in tail position if its value is the value of the whole body
+    fn walk(&mut self, expr: &Sexpr, tail: bool) -> Result<Sexpr> {
+        let items = match expr {
+            Sexpr::Symbol(s) if s == &self.f => {
+                return Err(Error::NotStructurallyRecursive(Core::from(expr)))
+            }
+            Sexpr::List(items) => items,
+            _ => return Ok(expr.clone()),
+        };
+
+        match &items[..] {
+            [Sexpr::Symbol(q), _] if q == &"quote" => Ok(expr.clone()),
+
+            [Sexpr::Symbol(f), args @ ..] if f == &self.f => self.recursive_call(expr, args),
+
+            [l @ Sexpr::Symbol(s), Sexpr::List(xs), body] if s == &"λ" || s == &"lambda" => {
+                let binders: Vec<_> = xs.iter().filter_map(binder_name).cloned().collect();
+                let body = self.shadowed(&binders, |this| this.walk(body, false))?;
+                Ok(Sexpr::List(vec![l.clone(), Sexpr::List(xs.clone()), body]))
+            }
+
+            [b @ Sexpr::Symbol(s), Sexpr::List(bs), body]
+                if ["Π", "Pi", "∏", "∀", "Σ", "Sigma"].contains(&s.name()) =>
+            {
+                let mut binders = vec![];
+                let mut bs_out = vec![];
+                for binder in bs {
+                    match binder {
+                        Sexpr::List(xt) if xt.len() == 2 => {
+                            let t = self.shadowed(&binders, |this| this.walk(&xt[1], false))?;
+                            bs_out.push(Sexpr::List(vec![xt[0].clone(), t]));
+                            binders.extend(xt[0].as_symbol().cloned());
+                        }
+                        _ => bs_out.push(binder.clone()),
+                    }
+                }
+                let body = self.shadowed(&binders, |this| this.walk(body, false))?;
+                Ok(Sexpr::List(vec![b.clone(), Sexpr::List(bs_out), body]))
+            }
+
+            // a let's body has the value of the whole let, so it stays in tail position
+            [l @ Sexpr::Symbol(s), Sexpr::List(bindings), body] if s == &"let" || s == &"let*" => {
+                let sequential = s == &"let*";
+                let mut binders = vec![];
+                let mut bindings_out = vec![];
+                for binding in bindings {
+                    match binding {
+                        Sexpr::List(xe) if xe.len() == 2 => {
+                            let scope: &[Symbol] = if sequential { &binders } else { &[] };
+                            let e = self.shadowed(scope, |this| this.walk(&xe[1], false))?;
+                            bindings_out.push(Sexpr::List(vec![xe[0].clone(), e]));
+                            binders.extend(xe[0].as_symbol().cloned());
+                        }
+                        _ => bindings_out.push(binding.clone()),
+                    }
+                }
+                let body = self.shadowed(&binders, |this| this.walk(body, tail))?;
+                Ok(Sexpr::List(vec![
+                    l.clone(),
+                    Sexpr::List(bindings_out),
+                    body,
+                ]))
+            }
+
+            [m @ Sexpr::Symbol(s), target, clauses @ ..] if s == &"match" => {
+                let target_out = self.walk(target, false)?;
+                let param = target
+                    .as_symbol()
+                    .and_then(|x| self.params.iter().position(|p| p.as_ref() == Some(x)));
+
+                let saved_hypotheses = self.hypotheses.clone();
+                if tail && param.is_some() {
+                    // this match recurses on its own, so the hypotheses of the outer ones
+                    // would be for the original value of its target rather than the current one
+                    for (_, ih) in self.hypotheses.values_mut() {
+                        *ih = None;
+                    }
+                }
+                let mut out = vec![m.clone(), target_out];
+                for clause in clauses {
+                    out.push(self.clause(param, clause, tail)?);
+                }
+                self.hypotheses = saved_hypotheses;
+                Ok(Sexpr::List(out))
+            }
+
+            _ => Ok(Sexpr::List(
+                items
+                    .iter()
+                    .map(|x| self.walk(x, false))
+                    .collect::<Result<_>>()?,
+            )),
+        }
+    }
+
+    fn recursive_call(&mut self, call: &Sexpr, args: &[Sexpr]) -> Result<Sexpr> {
+        let n = self.params.len();
+        if args.len() < n {
+            return Err(Error::NotStructurallyRecursive(Core::from(call)));
+        }
+
+        let (args, extra) = args.split_at(n);
+        for (i, arg) in args.iter().enumerate() {
+            let ih = match arg.as_symbol().and_then(|x| self.hypotheses.get(x)) {
+                Some((param, ih)) if *param == i => ih.clone(),
+                _ => continue,
+            };
+
+            let others_unchanged = args.iter().enumerate().all(|(j, a)| {
+                j == i
+                    || match (&self.params[j], a.as_symbol()) {
+                        (Some(p), Some(x)) => p == x,
+                        _ => false,
+                    }
+            });
+
+            if others_unchanged {
+                let ih = match ih {
+                    Some(ih) => Sexpr::Symbol(ih),
+                    None => return Err(Error::NotInStructuralPosition(Core::from(call))),
+                };
+                if extra.is_empty() {
+                    return Ok(ih);
+                }
+                let mut app = vec![ih];
+                for x in extra {
+                    app.push(self.walk(x, false)?);
+                }
+                return Ok(Sexpr::List(app));
+            }
+        }
+
+        Err(Error::NotStructurallyRecursive(Core::from(call)))
+    }
+
+    /// Walk a match clause. If the match is on parameter `param`, the recursive fields of the
+    /// clause's constructor may be passed to recursive calls in place of the parameter, as
+    /// long as the match is in tail position.
+    fn clause(&mut self, param: Option<usize>, clause: &Sexpr, tail: bool) -> Result<Sexpr> {
+        let (pattern, body) = match clause {
+            Sexpr::List(pb) if pb.len() == 2 => (&pb[0], &pb[1]),
+            _ => return Ok(clause.clone()),
+        };
+
+        let mut pattern: Vec<Sexpr> = match pattern {
+            Sexpr::List(p) => p.clone(),
+            _ => return Ok(Sexpr::List(vec![pattern.clone(), self.walk(body, tail)?])),
+        };
+
+        let recursive_field = match pattern.first().and_then(Sexpr::as_symbol) {
+            Some(c) if c == &"add1" => Some(1),
+            Some(c) if c == &"::" => Some(2),
+            _ => None,
+        };
+
+        let binders: Vec<_> = pattern
+            .iter()
+            .skip(1)
+            .filter_map(Sexpr::as_symbol)
+            .cloned()
+            .collect();
+
+        let hypothesis = match (param, recursive_field) {
+            (Some(param), Some(field)) if field < pattern.len() => {
+                let ih = match pattern.get(field + 1).and_then(Sexpr::as_symbol) {
+                    _ if !tail => None,
+                    Some(ih) => Some(ih.clone()),
+                    None => {
+                        let ih = freshen(&self.used, &"ih".into());
+                        self.used.insert(ih.clone());
+                        pattern.push(Sexpr::Symbol(ih.clone()));
+                        Some(ih)
+                    }
+                };
+                pattern[field]
+                    .as_symbol()
+                    .cloned()
+                    .map(|x| (x, (param, ih)))
+            }
+            _ => None,
+        };
+
+        let body = self.shadowed(&binders, |this| {
+            this.hypotheses.extend(hypothesis);
+            this.walk(body, tail)
+        })?;
+
+        Ok(Sexpr::List(vec![Sexpr::List(pattern), body]))
+    }
+
+    /// Walk with `binders` in scope; they hide the function, parameters and fields of the same name
+    fn shadowed(
+        &mut self,
+        binders: &[Symbol],
+        walk: impl FnOnce(&mut Self) -> Result<Sexpr>,
+    ) -> Result<Sexpr> {
+        let saved_params = self.params.clone();
+        let saved_hypotheses = self.hypotheses.clone();
+        let saved_f = self.f.clone();
+
+        for x in binders {
+            for p in self.params.iter_mut() {
+                if p.as_ref() == Some(x) {
+                    *p = None;
+                }
+            }
+            self.hypotheses.remove(x);
+            if x == &self.f {
+                // an inner binding of the function's own name is an ordinary variable
+                self.f = freshen(&self.used, x);
+            }
+        }
+
+        let result = walk(self);
+        self.params = saved_params;
+        self.hypotheses = saved_hypotheses;
+        self.f = saved_f;
+        result
+    }
+}
+
+/// The name of a λ parameter, which may be written with a type as `(x A)`
+fn binder_name(param: &Sexpr) -> Option<&Symbol> {
+    match param {
+        Sexpr::List(xt) if xt.len() == 2 => xt[0].as_symbol(),
+        _ => param.as_symbol(),
+    }
+}
+
+fn symbols(expr: &Sexpr) -> HashSet<Symbol> {
+    match expr {
+        Sexpr::Symbol(s) => [s.clone()].into_iter().collect(),
+        Sexpr::List(items) => items.iter().flat_map(symbols).collect(),
+        _ => HashSet::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::basics::{Core, Ctx};
+    use crate::errors::Error;
+    use crate::rep::{eval_normalize, norm};
+
+    fn define_rec(ctx: &mut Ctx, name: &str, t: &str, v: &str) -> Result<(), String> {
+        eval_normalize(ctx, &format!("(claim {} {})", name, t))?;
+        eval_normalize(ctx, &format!("(define-rec {} {})", name, v))?;
+        Ok(())
+    }
+
+    fn evaluates_to(ctx: &Ctx, e: &str, t: &str, v: &str) {
+        assert_eq!(
+            norm(ctx, &e.parse().unwrap()),
+            Ok(Core::the(t.parse().unwrap(), v.parse().unwrap()))
+        );
+    }
+
+    #[test]
+    fn recursion_on_nat() {
+        let mut ctx = Ctx::new();
+        define_rec(
+            &mut ctx,
+            "+",
+            "(-> Nat Nat Nat)",
+            "(λ (n j) (match n (zero j) ((add1 n-1) (add1 (+ n-1 j)))))",
+        )
+        .unwrap();
+        evaluates_to(&ctx, "(+ 2 3)", "Nat", "5");
+    }
+
+    #[test]
+    fn recursion_on_list_with_type_parameter() {
+        let mut ctx = Ctx::new();
+        define_rec(
+            &mut ctx,
+            "length",
+            "(Π ((E U)) (-> (List E) Nat))",
+            "(λ (E es) (match es (nil 0) ((:: e rest) (add1 (length E rest)))))",
+        )
+        .unwrap();
+        define_rec(
+            &mut ctx,
+            "append",
+            "(Π ((E U)) (-> (List E) (List E) (List E)))",
+            "(λ (E start end)
+               (match start (nil end) ((:: e es) (:: e (append E es end)))))",
+        )
+        .unwrap();
+        evaluates_to(
+            &ctx,
+            "(length Atom (append Atom (:: 'a (:: 'b nil)) (:: 'c nil)))",
+            "Nat",
+            "3",
+        );
+    }
+
+    #[test]
+    fn recursive_calls_may_return_functions() {
+        let mut ctx = Ctx::new();
+        define_rec(
+            &mut ctx,
+            "twice",
+            "(-> Nat Nat Nat)",
+            "(λ (n) (match n (zero (λ (j) j)) ((add1 n-1) (λ (j) (add1 (add1 (twice n-1 j)))))))",
+        )
+        .unwrap();
+        evaluates_to(&ctx, "(twice 2 1)", "Nat", "5");
+    }
+
+    #[test]
+    fn calls_through_a_match_that_is_not_the_body_are_rejected() {
+        let mut ctx = Ctx::new();
+        // the hypothesis is the match's result on k, which is one less than (f k)
+        assert_eq!(
+            define_rec(
+                &mut ctx,
+                "f",
+                "(-> Nat Nat)",
+                "(λ (n) (add1 (match n (zero 0) ((add1 k) (f k)))))"
+            ),
+            Err(Error::NotInStructuralPosition("(f k)".parse().unwrap()).to_string())
+        );
+        assert_eq!(
+            define_rec(
+                &mut ctx,
+                "g",
+                "(-> Nat Nat Nat)",
+                "(λ (n j)
+                   (match n
+                     (zero j)
+                     ((add1 k) (match j (zero (g k j)) ((add1 i) (add1 (g n i)))))))"
+            ),
+            Err(Error::NotInStructuralPosition("(g k j)".parse().unwrap()).to_string())
+        );
+        define_rec(
+            &mut ctx,
+            "h",
+            "(-> Nat Nat Nat)",
+            "(λ (n j)
+               (match n
+                 (zero j)
+                 ((add1 k) (match j (zero n) ((add1 i) (add1 (h n i)))))))",
+        )
+        .unwrap();
+        evaluates_to(&ctx, "(h 2 3)", "Nat", "5");
+    }
+
+    #[test]
+    fn let_bindings_shadow_recursive_fields() {
+        let mut ctx = Ctx::new();
+        let not_structural =
+            |call: &str| Err(Error::NotStructurallyRecursive(call.parse().unwrap()).to_string());
+
+        assert_eq!(
+            define_rec(
+                &mut ctx,
+                "f",
+                "(-> Nat Nat)",
+                "(λ (n) (match n (zero 0) ((add1 k) (let ((k (add1 (add1 k)))) (add1 (f k))))))"
+            ),
+            not_structural("(f k)")
+        );
+        assert_eq!(
+            define_rec(
+                &mut ctx,
+                "g",
+                "(-> Nat Nat)",
+                "(λ (n) (match n (zero 0) ((add1 k) (let* ((j k) (k (add1 j))) (add1 (g k))))))"
+            ),
+            not_structural("(g k)")
+        );
+        define_rec(
+            &mut ctx,
+            "h",
+            "(-> Nat Nat)",
+            "(λ (n) (match n (zero 0) ((add1 k) (let ((j k)) (add1 (h k))))))",
+        )
+        .unwrap();
+        evaluates_to(&ctx, "(h 3)", "Nat", "3");
+    }
+
+    #[test]
+    fn non_structural_calls_are_rejected() {
+        let mut ctx = Ctx::new();
+        let not_structural =
+            |call: &str| Err(Error::NotStructurallyRecursive(call.parse().unwrap()).to_string());
+
+        assert_eq!(
+            define_rec(
+                &mut ctx,
+                "loop",
+                "(-> Nat Nat)",
+                "(λ (n) (match n (zero 0) ((add1 n-1) (loop n))))"
+            ),
+            not_structural("(loop n)")
+        );
+        assert_eq!(
+            define_rec(
+                &mut ctx,
+                "swap",
+                "(-> Nat Nat Nat)",
+                "(λ (n j) (match n (zero j) ((add1 n-1) (swap n-1 n-1))))"
+            ),
+            not_structural("(swap n-1 n-1)")
+        );
+        assert_eq!(
+            define_rec(&mut ctx, "self", "(-> Nat Nat)", "(λ (n) (self n))"),
+            not_structural("(self n)")
+        );
+        assert_eq!(
+            define_rec(
+                &mut ctx,
+                "escape",
+                "(-> Nat (-> Nat Nat))",
+                "(λ (n) escape)"
+            ),
+            not_structural("escape")
+        );
+    }
+}
@@ -1,12 +1,22 @@
-use crate::basics::{Core, CoreInterface, Ctx, Renaming};
+use crate::basics::{
+    parse_binders, Constructor, Core, CoreInterface, Ctx, DataDecl, Goal, RecordDecl, Renaming,
+    SyntaxRule,
+};
 use crate::errors::Result;
 use crate::normalize::{read_back, val_in_ctx};
+use crate::recursion::desugar_rec;
+use crate::section::Section;
 use crate::sexpr::Sexpr;
+use crate::syntax::expand;
 use crate::typechecker::convert;
+use crate::types::cores;
 use sexpr_matcher::match_sexpr;
 use sexpr_parser::parse;
 use std::result;
 
+/// How deeply nested the terms that `auto` and `search` look for may be
+const SEARCH_DEPTH: usize = 3;
+
 pub fn norm_type(ctx: &Ctx, e: &Core) -> Result<Core> {
     let e_out = e.is_type(ctx, &Renaming::new())?;
     val_in_ctx(ctx, &e_out).read_back_type(ctx)
@@ -42,7 +52,10 @@ pub fn check_same(ctx: &Ctx, t: &Core, a: &Core, b: &Core) -> Result<()> {
 }
 
 pub fn eval_normalize(ctx: &mut Ctx, src: &str) -> result::Result<Option<Core>, String> {
-    let sexpr = parse::<Sexpr>(&src).map_err(|e| e.to_string())?;
+    let sexpr = parse::<Sexpr>(&src)
+        .map_err(|e| e.to_string())?
+        .name_holes();
+    let sexpr = expand(ctx, &sexpr).map_err(|e| e.to_string())?;
 
     match_sexpr!(
         &sexpr,
@@ -54,6 +67,27 @@ pub fn eval_normalize(ctx: &mut Ctx, src: &str) -> result::Result<Option<Core>,
             *ctx = ctx.define(ident.clone(), expr.into()).map_err(|e| e.to_string())?;
             return Ok(None);
         },
+        case ("define-opaque", [Sexpr::Symbol(ident)], expr) => {
+            *ctx = ctx.define_opaque(ident.clone(), expr.into()).map_err(|e| e.to_string())?;
+            return Ok(None);
+        },
+        case ("define-rec", [Sexpr::Symbol(ident)], expr) => {
+            let expr = desugar_rec(ident, expr).map_err(|e| e.to_string())?;
+            *ctx = ctx.define(ident.clone(), (&expr).into()).map_err(|e| e.to_string())?;
+            return Ok(None);
+        },
+        case ("postulate", [Sexpr::Symbol(ident)], expr) => {
+            *ctx = ctx.postulate(ident.clone(), expr.into()).map_err(|e| e.to_string())?;
+            return Ok(None);
+        },
+        case ("postulates", [Sexpr::Symbol(ident)]) => {
+            let names = ctx.postulates_used_by(ident).map_err(|e| e.to_string())?;
+            let atoms = names
+                .into_iter()
+                .rev()
+                .fold(cores::nil(), |es, x| cores::list_cons(cores::quote(x), es));
+            return Ok(Some(Core::the(cores::list(cores::atom()), atoms)));
+        },
         case ("reclaim", [Sexpr::Symbol(ident)], expr) => {
             *ctx = ctx.reclaim(ident.clone(), expr.into()).map_err(|e| e.to_string())?;
             return Ok(None);
@@ -62,6 +96,67 @@ pub fn eval_normalize(ctx: &mut Ctx, src: &str) -> result::Result<Option<Core>,
             *ctx = ctx.redefine(ident.clone(), expr.into()).map_err(|e| e.to_string())?;
             return Ok(None);
         },
+        case ("refine", [Sexpr::Symbol(ident)], [Sexpr::Symbol(hole)], expr) => {
+            *ctx = ctx.refine(ident.clone(), hole, expr.into()).map_err(|e| e.to_string())?;
+            return Ok(None);
+        },
+        case ("split", [Sexpr::Symbol(ident)], [Sexpr::Symbol(hole)], [Sexpr::Symbol(x)]) => {
+            *ctx = ctx.split(ident.clone(), hole, x.clone()).map_err(|e| e.to_string())?;
+            return Ok(None);
+        },
+        case ("auto", [Sexpr::Symbol(ident)], [Sexpr::Symbol(hole)]) => {
+            *ctx = ctx.auto(ident.clone(), hole, SEARCH_DEPTH).map_err(|e| e.to_string())?;
+            return Ok(None);
+        },
+        case ("search", t) => {
+            // report the term as it was found, rather than its normal form
+            let e = Core::the(t.into(), cores::search(SEARCH_DEPTH));
+            let (_, e_out) = e.synth(ctx, &Renaming::new()).map_err(|e| e.to_string())?;
+            return Ok(Some(e_out));
+        },
+        case ("check-same", t, a, b) => {
+            let (t, a, b) = (Core::from(t), Core::from(a), Core::from(b));
+            check_same(ctx, &t, &a, &b).map_err(|e| e.to_string())?;
+            // report success with the evidence that they are the same
+            let evidence = Core::the(cores::equal(t, a.clone(), b), cores::same(a));
+            return norm(ctx, &evidence).map(Some).map_err(|e| e.to_string());
+        },
+        case ("define-syntax", [Sexpr::List(pattern)], template) => {
+            let (name, params) = match pattern.split_first() {
+                Some((Sexpr::Symbol(name), params)) => (name, params),
+                _ => return Err(format!("Invalid macro pattern: {}", Sexpr::List(pattern.clone()))),
+            };
+            let rule = SyntaxRule::new(ctx, params, template.clone()).map_err(|e| e.to_string())?;
+            *ctx = ctx.define_syntax(name.clone(), rule).map_err(|e| e.to_string())?;
+            return Ok(None);
+        },
+        case ("section", [Sexpr::List(params)] :: [stmts]) => {
+            let mut section = Section::new(parse_binders(params));
+            let mut inner = ctx.clone();
+            for stmt in stmts {
+                inner = eval_section_stmt(&mut section, &inner, stmt)?;
+            }
+            *ctx = inner;
+            return Ok(None);
+        },
+        case ("data", [Sexpr::Symbol(name)], [Sexpr::List(params)], [Sexpr::List(indices)] :: [ctors]) => {
+            let decl = DataDecl {
+                name: name.clone(),
+                params: parse_binders(params),
+                indices: parse_binders(indices),
+                constructors: ctors.iter().map(parse_constructor).collect::<result::Result<_, _>>()?,
+            };
+            *ctx = ctx.data(decl).map_err(|e| e.to_string())?;
+            return Ok(None);
+        },
+        case ("record", [Sexpr::Symbol(name)], [Sexpr::List(fields)]) => {
+            let decl = RecordDecl {
+                name: name.clone(),
+                fields: parse_binders(fields),
+            };
+            *ctx = ctx.record(decl).map_err(|e| e.to_string())?;
+            return Ok(None);
+        },
         else => {},
     );
 
@@ -70,12 +165,52 @@ pub fn eval_normalize(ctx: &mut Ctx, src: &str) -> result::Result<Option<Core>,
         .map_err(|e| e.to_string())
 }
 
+/// Evaluate `src` like `eval_normalize`, and report the goals of the holes that it contains
+pub fn eval_with_goals(
+    ctx: &mut Ctx,
+    src: &str,
+) -> (result::Result<Option<Core>, String>, Vec<Goal>) {
+    *ctx = ctx.collecting_goals();
+    let result = eval_normalize(ctx, src);
+    (result, ctx.take_goals())
+}
+
+fn eval_section_stmt(
+    section: &mut Section,
+    ctx: &Ctx,
+    stmt: &Sexpr,
+) -> result::Result<Ctx, String> {
+    match_sexpr!(
+        stmt,
+        case ("claim", [Sexpr::Symbol(ident)], expr) => {
+            section.claim(ctx, ident.clone(), expr.into()).map_err(|e| e.to_string())
+        },
+        case ("define", [Sexpr::Symbol(ident)], expr) => {
+            section.define(ctx, ident.clone(), expr.into()).map_err(|e| e.to_string())
+        },
+        else => Err(format!("Invalid section statement: {}", stmt)),
+    )
+}
+
+fn parse_constructor(sexpr: &Sexpr) -> result::Result<Constructor, String> {
+    match_sexpr!(
+        sexpr,
+        case ([Sexpr::Symbol(name)], [Sexpr::List(args)], result) => {
+            Ok(Constructor {
+                name: name.clone(),
+                args: parse_binders(args),
+                result: result.into(),
+            })
+        },
+        else => Err(format!("Invalid constructor: {}", sexpr)),
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     use crate::errors::Error;
-    use crate::types::cores;
     use lazy_static::lazy_static;
 
     lazy_static! {
@@ -99,8 +234,11 @@ mod tests {
     }
 
     #[test]
-    fn u_does_not_have_a_type() {
-        assert_eq!(rep(&CTX, &cores::universe()), Err(Error::UhasNoType));
+    fn u_is_a_u1() {
+        assert_eq!(
+            rep(&CTX, &cores::universe()),
+            Ok(Core::the(cores::universe_level(1), cores::universe()))
+        );
     }
 
     #[test]
@@ -250,6 +388,238 @@ mod tests {
         );
     }
 
+    #[test]
+    fn check_same_as_a_top_level_form() {
+        let mut ctx = CTX.clone();
+        assert_eq!(
+            eval_normalize(&mut ctx, "(check-same Nat (add1 2) 3)"),
+            Ok(Some(Core::the(
+                cores::equal(cores::nat(), cores::the_nat(3), cores::the_nat(3)),
+                cores::same(cores::the_nat(3))
+            )))
+        );
+        assert_eq!(
+            eval_normalize(&mut ctx, "(check-same Atom 'apple 'pear)"),
+            Err(
+                Error::NotTheSame(cores::atom(), Core::quote("apple"), Core::quote("pear"))
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn holes_are_reported_as_goals() {
+        let mut ctx = CTX.clone();
+        eval_normalize(&mut ctx, "(claim f (Π ((A U)) (-> A (List A))))").unwrap();
+        let (result, goals) = eval_with_goals(&mut ctx, "(define f (λ (A a) (:: a TODO)))");
+        assert_eq!(result, Ok(None));
+        assert_eq!(
+            goals,
+            vec![Goal {
+                name: "TODO-f-1".into(),
+                goal: cores::list(cores::refer("A")),
+                context: vec![
+                    ("A".into(), cores::universe()),
+                    ("a".into(), cores::refer("A"))
+                ],
+            }]
+        );
+
+        let (_, goals) = eval_with_goals(&mut ctx, "(cons (the Nat TODO) (the Atom TODO))");
+        let names: Vec<_> = goals.iter().map(|g| g.name.name().to_string()).collect();
+        assert_eq!(names, vec!["TODO-1", "TODO-2"]);
+        assert!(goals.iter().all(|g| g.context.is_empty()));
+
+        let (_, goals) = eval_with_goals(
+            &mut ctx,
+            "(the (-> Nat Atom) (λ (n) (let ((m (add1 n))) TODO)))",
+        );
+        assert_eq!(
+            goals[0].context,
+            vec![("n".into(), cores::nat()), ("m".into(), cores::nat())]
+        );
+    }
+
+    #[test]
+    fn declarations_are_not_local_variables_of_goals() {
+        let mut ctx = CTX.clone();
+        eval_normalize(
+            &mut ctx,
+            "(data Tree ((A U)) ()
+               (leaf () (Tree A))
+               (node ((l (Tree A)) (x A) (r (Tree A))) (Tree A)))",
+        )
+        .unwrap();
+        let (_, goals) = eval_with_goals(&mut ctx, "(the (-> Nat (Tree Nat)) (λ (n) TODO))");
+        assert_eq!(goals[0].context, vec![("n".into(), cores::nat())]);
+    }
+
+    #[test]
+    fn holes_are_refined_in_place() {
+        let mut ctx = CTX.clone();
+        eval_normalize(&mut ctx, "(claim f (Π ((A U)) (-> A (List A))))").unwrap();
+        eval_normalize(&mut ctx, "(define f (λ (A a) (:: a TODO)))").unwrap();
+
+        assert_eq!(
+            eval_normalize(&mut ctx, "(refine f TODO-f-1 a)"),
+            Err(Error::WrongType(cores::refer("A"), cores::list(cores::refer("A"))).to_string())
+        );
+        // f is not finished, so neither it nor what is defined with it can fill its holes
+        assert_eq!(
+            eval_normalize(&mut ctx, "(refine f TODO-f-1 (f A a))"),
+            Err(Error::UnknownVariable("f".into()).to_string())
+        );
+        eval_normalize(&mut ctx, "(claim g (Π ((A U)) (-> A (List A))))").unwrap();
+        eval_normalize(&mut ctx, "(define g f)").unwrap();
+        assert_eq!(
+            eval_normalize(&mut ctx, "(refine f TODO-f-1 (g A a))"),
+            Err(Error::UnknownVariable("g".into()).to_string())
+        );
+        let (result, goals) = eval_with_goals(&mut ctx, "(refine f TODO-f-1 (:: a TODO))");
+        assert_eq!(result, Ok(None));
+        let names: Vec<_> = goals.iter().map(|g| g.name.name().to_string()).collect();
+        assert_eq!(names, vec!["TODO-f-1-1"]);
+
+        eval_normalize(&mut ctx, "(refine f TODO-f-1-1 nil)").unwrap();
+        assert_eq!(
+            eval_normalize(&mut ctx, "(f Atom 'pea)")
+                .unwrap()
+                .unwrap()
+                .to_string(),
+            "(the (List Atom) (:: 'pea (:: 'pea nil)))"
+        );
+        assert_eq!(
+            eval_normalize(&mut ctx, "(refine f TODO-f-1-1 nil)"),
+            Err(Error::NoSuchHole("TODO-f-1-1".into(), "f".into()).to_string())
+        );
+    }
+
+    #[test]
+    fn holes_are_split_into_cases() {
+        let mut ctx = CTX.clone();
+        eval_normalize(&mut ctx, "(claim double (-> Nat Nat))").unwrap();
+        eval_normalize(&mut ctx, "(define double (λ (n) TODO))").unwrap();
+        let (result, goals) = eval_with_goals(&mut ctx, "(split double TODO-double-1 n)");
+        assert_eq!(result, Ok(None));
+        assert_eq!(
+            goals.iter().map(|g| g.to_string()).collect::<Vec<_>>(),
+            vec![
+                "n : Nat\n----------------\nTODO-double-1-zero : Nat",
+                "n : Nat\nn-1 : Nat\nih : Nat\n----------------\nTODO-double-1-add1 : Nat",
+            ]
+        );
+
+        eval_normalize(&mut ctx, "(refine double TODO-double-1-zero 0)").unwrap();
+        eval_normalize(
+            &mut ctx,
+            "(refine double TODO-double-1-add1 (add1 (add1 ih)))",
+        )
+        .unwrap();
+        assert_eq!(
+            eval_normalize(&mut ctx, "(double 3)"),
+            Ok(Some(Core::the(cores::nat(), cores::the_nat(6))))
+        );
+    }
+
+    #[test]
+    fn case_splits_infer_the_motive_from_the_hole() {
+        let mut ctx = CTX.clone();
+        eval_normalize(
+            &mut ctx,
+            "(claim v= (Π ((k Nat)) (-> (Vec Atom k) (= Nat k k))))",
+        )
+        .unwrap();
+        eval_normalize(&mut ctx, "(define v= (λ (k es) TODO))").unwrap();
+        let (_, goals) = eval_with_goals(&mut ctx, "(split v= TODO-v=-1 es)");
+        let types: Vec<_> = goals.iter().map(|g| g.goal.to_string()).collect();
+        assert_eq!(types, vec!["(= Nat 0 0)", "(= Nat (add1 k₁) (add1 k₁))"]);
+
+        assert_eq!(
+            eval_normalize(&mut ctx, "(split v= TODO-v=-1-vecnil k)"),
+            Ok(None)
+        );
+        eval_normalize(&mut ctx, "(claim not-inductive (-> Atom Atom))").unwrap();
+        eval_normalize(&mut ctx, "(define not-inductive (λ (a) TODO))").unwrap();
+        assert_eq!(
+            eval_normalize(&mut ctx, "(split not-inductive TODO-not-inductive-1 a)"),
+            Err(Error::CantMatchOn(cores::atom()).to_string())
+        );
+    }
+
+    #[test]
+    fn search_finds_simple_proofs() {
+        let mut ctx = CTX.clone();
+        let found = |ctx: &mut Ctx, t: &str| {
+            eval_normalize(ctx, &format!("(search {})", t)).map(|e| e.unwrap().to_string())
+        };
+        assert_eq!(
+            found(&mut ctx, "(Pair Trivial (= Nat 2 2))"),
+            Ok("(cons sole (same 2))".to_string())
+        );
+        assert_eq!(
+            found(&mut ctx, "(Π ((A U) (a A)) (Pair A A))"),
+            Ok("(λ (A) (λ (a) (cons a a)))".to_string())
+        );
+        assert_eq!(
+            found(&mut ctx, "(Either (= Nat 1 2) (= Nat 2 2))"),
+            Ok("(right (same 2))".to_string())
+        );
+        assert_eq!(found(&mut ctx, "(Vec Atom 0)"), Ok("vecnil".to_string()));
+        assert_eq!(
+            found(&mut ctx, "(Vec Trivial 2)"),
+            Ok("(vec:: sole (vec:: sole vecnil))".to_string())
+        );
+        assert_eq!(
+            found(&mut ctx, "(Vec Atom 2)"),
+            Ok("(vec:: 'a (vec:: 'a vecnil))".to_string())
+        );
+        assert_eq!(
+            found(&mut ctx, "(= Nat 1 2)"),
+            Err(Error::NoProofFound("(= Nat 1 2)".parse().unwrap()).to_string())
+        );
+    }
+
+    #[test]
+    fn search_passes_over_arguments_it_cannot_use() {
+        let mut ctx = CTX.clone();
+        for stmt in [
+            "(claim + (-> Nat Nat Nat))",
+            "(define + (λ (n j) (iter-Nat n j (λ (k) (add1 k)))))",
+            "(claim * (-> Nat Nat Nat))",
+            "(define * (λ (n j) (rec-Nat n 0 (λ (n-1 k) (+ j k)))))",
+        ] {
+            eval_normalize(&mut ctx, stmt).unwrap();
+        }
+        eval_normalize(&mut ctx, "(claim lem (Π ((n Nat)) (= Nat n n)))").unwrap();
+        eval_normalize(&mut ctx, "(define lem (λ (n) (same n)))").unwrap();
+        assert_eq!(
+            eval_normalize(&mut ctx, "(search (Π ((c Nat)) (= Nat c 5)))"),
+            Err(Error::NoProofFound("(Π ((c Nat)) (= Nat c 5))".parse().unwrap()).to_string())
+        );
+    }
+
+    #[test]
+    fn auto_applies_the_names_in_scope() {
+        let mut ctx = CTX.clone();
+        let lemma = "(Π ((n Nat)) (= Nat (iter-Nat n 0 (λ (k) (add1 k))) n))";
+        eval_normalize(&mut ctx, &format!("(postulate lem {})", lemma)).unwrap();
+        eval_normalize(&mut ctx, &format!("(claim p {})", lemma)).unwrap();
+        eval_normalize(&mut ctx, "(define p (λ (n) TODO))").unwrap();
+        eval_normalize(&mut ctx, "(auto p TODO-p-1)").unwrap();
+        assert_eq!(
+            eval_normalize(&mut ctx, "(p 3)").map(|e| e.unwrap().to_string()),
+            Ok("(the (= Nat 3 3) (lem 3))".to_string())
+        );
+
+        // the definition being refined is not finished, so it is not used
+        eval_normalize(&mut ctx, "(claim q Absurd)").unwrap();
+        eval_normalize(&mut ctx, "(define q TODO)").unwrap();
+        assert_eq!(
+            eval_normalize(&mut ctx, "(auto q TODO-q-1)"),
+            Err(Error::NoProofFound(cores::absurd()).to_string())
+        );
+    }
+
     #[test]
     fn function_application() {
         assert_eq!(
@@ -275,17 +645,28 @@ mod tests {
     }
 
     #[test]
-    fn expressions_without_type_are_only_normalized() {
-        assert_eq!(norm(&CTX, &"U".parse().unwrap()), Ok(cores::universe()));
+    fn types_of_types_live_in_higher_universes() {
+        let u1 = cores::universe_level(1);
+
+        assert_eq!(
+            norm(&CTX, &"U".parse().unwrap()),
+            Ok(Core::the(u1.clone(), cores::universe()))
+        );
 
         assert_eq!(
             norm(&CTX, &"(Pair U U)".parse().unwrap()),
-            Ok(cores::sigma("x", cores::universe(), cores::universe()))
+            Ok(Core::the(
+                u1.clone(),
+                cores::sigma("a", cores::universe(), cores::universe())
+            ))
         );
 
         assert_eq!(
             norm(&CTX, &"(-> U U)".parse().unwrap()),
-            Ok(cores::pi("x", cores::universe(), cores::universe()))
+            Ok(Core::the(
+                u1,
+                cores::pi("x", cores::universe(), cores::universe())
+            ))
         );
     }
 }
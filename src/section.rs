@@ -0,0 +1,133 @@
+//! Sections: claims and definitions that share parameters.
+//!
+//! In `(section ((E U)) (claim length (-> (List E) Nat)) (define length ...))` each claim
+//! is abstracted over the section's parameters, as in `(Π ((E U)) (-> (List E) Nat))`,
+//! and each definition is wrapped in a matching `λ`. Inside the section, the names it has
+//! already defined stand for their applications to the parameters, so they are used
+//! without passing the parameters along.
+
+use crate::basics::{Core, Ctx};
+use crate::errors::Result;
+use crate::symbol::Symbol;
+use crate::types::cores;
+
+pub struct Section {
+    params: Vec<(Symbol, Core)>,
+    defined: Vec<Symbol>,
+}
+
+impl Section {
+    pub fn new(params: Vec<(Symbol, Core)>) -> Self {
+        Section {
+            params,
+            defined: vec![],
+        }
+    }
+
+    pub fn claim(&self, ctx: &Ctx, name: impl Into<Symbol>, t: Core) -> Result<Ctx> {
+        ctx.claim(name, cores::pi_star(self.params.clone(), self.reapply(t)))
+    }
+
+    pub fn define(&mut self, ctx: &Ctx, name: impl Into<Symbol>, v: Core) -> Result<Ctx> {
+        let name = name.into();
+        let param_names = self.params.iter().map(|(x, _)| x.clone()).collect();
+        let ctx = ctx.define(
+            name.clone(),
+            cores::lambda_star(param_names, self.reapply(v)),
+        )?;
+        self.defined.push(name);
+        Ok(ctx)
+    }
+
+    /// Bind the names defined so far in the section to their applications to the parameters
+    fn reapply(&self, e: Core) -> Core {
+        if self.defined.is_empty() {
+            return e;
+        }
+        let args: Vec<_> = self
+            .params
+            .iter()
+            .map(|(x, _)| cores::refer(x.clone()))
+            .collect();
+        let bindings = self
+            .defined
+            .iter()
+            .map(|f| {
+                (
+                    f.clone(),
+                    cores::app_star(cores::refer(f.clone()), args.clone()),
+                )
+            })
+            .collect();
+        cores::let_(bindings, e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::basics::{Core, Ctx};
+    use crate::rep::{eval_normalize, norm};
+
+    fn evaluates_to(ctx: &Ctx, e: &str, t: &str, v: &str) {
+        assert_eq!(
+            norm(ctx, &e.parse().unwrap()),
+            Ok(Core::the(t.parse().unwrap(), v.parse().unwrap()))
+        );
+    }
+
+    #[test]
+    fn sections_abstract_over_their_parameters() {
+        let mut ctx = Ctx::new();
+        eval_normalize(
+            &mut ctx,
+            "(section ((E U))
+               (claim step-length (-> E (List E) Nat Nat))
+               (define step-length (λ (e es length-es) (add1 length-es)))
+               (claim length (-> (List E) Nat))
+               (define length (λ (es) (rec-List es 0 step-length))))",
+        )
+        .unwrap();
+        assert!(norm(
+            &ctx,
+            &"(the (Π ((E U)) (-> (List E) Nat)) length)"
+                .parse()
+                .unwrap()
+        )
+        .is_ok());
+        evaluates_to(&ctx, "(length Atom (:: 'a (:: 'b nil)))", "Nat", "2");
+    }
+
+    #[test]
+    fn sections_with_several_parameters() {
+        let mut ctx = Ctx::new();
+        eval_normalize(
+            &mut ctx,
+            "(section ((A U) (D U))
+               (claim swap (-> (Pair A D) (Pair D A)))
+               (define swap (λ (p) (cons (cdr p) (car p))))
+               (claim swap-twice (-> (Pair A D) (Pair A D)))
+               (define swap-twice (λ (p) ((the (-> (Pair D A) (Pair A D)) (λ (q) (cons (cdr q) (car q)))) (swap p)))))",
+        )
+        .unwrap();
+        evaluates_to(
+            &ctx,
+            "(cdr (swap-twice Nat Atom (cons 1 'a)))",
+            "Atom",
+            "'a",
+        );
+        assert!(eval_normalize(&mut ctx, "(swap (cons 1 'a))").is_err());
+    }
+
+    #[test]
+    fn failing_sections_leave_the_context_alone() {
+        let mut ctx = Ctx::new();
+        let result = eval_normalize(
+            &mut ctx,
+            "(section ((E U))
+               (claim first (-> (List E) E))
+               (define first (λ (es) 'oops)))",
+        );
+        assert!(result.is_err());
+        assert!(eval_normalize(&mut ctx, "(claim first Nat)").is_ok());
+    }
+}
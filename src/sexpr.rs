@@ -1,12 +1,15 @@
 use crate::symbol::Symbol;
+use num_bigint::{BigInt, BigUint, Sign};
 use sexpr_matcher::MaybeList;
 use sexpr_parser::SexprFactory;
 use std::fmt::{Display, Formatter};
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum Sexpr {
     Invalid(String),
-    SmallNat(u64),
+    Nat(BigUint),
+    /// A negative integer literal
+    Int(BigInt),
     Symbol(Symbol),
     List(Vec<Sexpr>),
 }
@@ -18,15 +21,79 @@ impl Sexpr {
             _ => None,
         }
     }
+
+    /// Name the anonymous holes, written as a bare `TODO`, by their position in reading
+    /// order: the first becomes `(TODO TODO-1)`, the second `(TODO TODO-2)`, and so on.
+    /// Holes in a claim or definition of `x` are numbered on their own, as `TODO-x-1`, ...
+    /// so that they are not mistaken for the holes of other definitions. Holes in the
+    /// expression that refines the hole `h` are numbered as `h-1`, ...
+    pub fn name_holes(&self) -> Sexpr {
+        self.name_holes_from("TODO-", &mut 0)
+    }
+
+    fn name_holes_from(&self, prefix: &str, count: &mut usize) -> Sexpr {
+        match self {
+            Sexpr::Symbol(s) if s.name() == "TODO" => {
+                *count += 1;
+                let name = Symbol::new(&format!("{}{}", prefix, count));
+                Sexpr::List(vec![self.clone(), Sexpr::Symbol(name)])
+            }
+            Sexpr::List(items) => match items.as_slice() {
+                [head, Sexpr::Symbol(_)] if head == "TODO" || head == "quote" => self.clone(),
+                [head, Sexpr::Symbol(x), rest @ ..] if is_defining_form(head) => {
+                    let prefix = format!("TODO-{}-", x.name());
+                    let mut count = 0;
+                    let rest = rest.iter().map(|e| e.name_holes_from(&prefix, &mut count));
+                    Sexpr::List(
+                        vec![head.clone(), items[1].clone()]
+                            .into_iter()
+                            .chain(rest)
+                            .collect(),
+                    )
+                }
+                [head, x, Sexpr::Symbol(hole), e] if head == "refine" => Sexpr::List(vec![
+                    head.clone(),
+                    x.clone(),
+                    items[2].clone(),
+                    e.name_holes_from(&format!("{}-", hole.name()), &mut 0),
+                ]),
+                _ => Sexpr::List(
+                    items
+                        .iter()
+                        .map(|e| e.name_holes_from(prefix, count))
+                        .collect(),
+                ),
+            },
+            _ => self.clone(),
+        }
+    }
+}
+
+fn is_defining_form(head: &Sexpr) -> bool {
+    [
+        "claim",
+        "define",
+        "define-opaque",
+        "define-rec",
+        "postulate",
+        "reclaim",
+        "redefine",
+    ]
+    .iter()
+    .any(|form| head == form)
 }
 
 impl SexprFactory for Sexpr {
     type Sexpr = Sexpr;
-    type Integer = u64;
+    type Integer = BigInt;
     type Float = f64;
 
-    fn int(x: u64) -> Self::Sexpr {
-        Sexpr::SmallNat(x)
+    fn int(x: BigInt) -> Self::Sexpr {
+        if x.sign() == Sign::Minus {
+            Sexpr::Int(x)
+        } else {
+            Sexpr::Nat(x.into_parts().1)
+        }
     }
 
     fn float(x: f64) -> Self::Sexpr {
@@ -53,7 +120,7 @@ impl SexprFactory for Sexpr {
 impl PartialEq<u64> for Sexpr {
     fn eq(&self, other: &u64) -> bool {
         match self {
-            Sexpr::SmallNat(n) => n == other,
+            Sexpr::Nat(n) => n == &BigUint::from(*other),
             _ => false,
         }
     }
@@ -84,7 +151,8 @@ impl Display for Sexpr {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
             Sexpr::Invalid(s) => write!(f, "{}", s),
-            Sexpr::SmallNat(x) => write!(f, "{}", x),
+            Sexpr::Nat(x) => write!(f, "{}", x),
+            Sexpr::Int(x) => write!(f, "{}", x),
             Sexpr::Symbol(s) => write!(f, "{}", s.name()),
             Sexpr::List(l) => {
                 write!(f, "(")?;
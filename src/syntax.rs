@@ -0,0 +1,328 @@
+//! Syntax macros.
+//!
+//! `(define-syntax (name param ...) template)` adds a notation to the language: every later
+//! `(name arg ...)` is replaced with the template, with the arguments in place of the
+//! parameters, before the expression is parsed. The template's other names either refer to
+//! what is in scope where the macro is defined, or are bound by the template itself. The
+//! latter are renamed apart from the names in the arguments at each use, so that a macro
+//! never captures the variables of the expressions it is given. Conversely, a variable at the
+//! use site that has the same name as a global that a template refers to is renamed before
+//! the macro is expanded, so that it does not capture the template's reference either.
+
+use crate::basics::{is_var_name, Core, Ctx};
+use crate::errors::{Error, Result};
+use crate::fresh::freshen;
+use crate::sexpr::Sexpr;
+use crate::symbol::Symbol;
+use std::collections::{HashMap, HashSet};
+
+#[derive(Debug, PartialEq)]
+pub struct SyntaxRule {
+    params: Vec<Symbol>,
+    template: Sexpr,
+    /// The names that the template binds itself, in the order they occur
+    locals: Vec<Symbol>,
+    /// The names in scope where the macro is defined that the template refers to
+    globals: HashSet<Symbol>,
+}
+
+impl SyntaxRule {
+    /// A macro with the given parameters, whose template may refer to the names in `ctx`
+    pub fn new(ctx: &Ctx, params: &[Sexpr], template: Sexpr) -> Result<Self> {
+        let mut names = vec![];
+        for param in params {
+            match param {
+                Sexpr::Symbol(x) if !is_var_name(x) => return Err(Error::NotAVarName(x.clone())),
+                Sexpr::Symbol(x) if names.contains(x) => {
+                    return Err(Error::DuplicateParameter(x.clone()))
+                }
+                Sexpr::Symbol(x) => names.push(x.clone()),
+                _ => return Err(Error::InvalidSyntax(param.to_string().into())),
+            }
+        }
+
+        let in_scope = ctx.names_only();
+        let mut locals = vec![];
+        let mut globals = HashSet::new();
+        for x in symbols_in_order(&template) {
+            if !is_var_name(&x) || names.contains(&x) {
+                continue;
+            }
+            if in_scope.contains(&x) {
+                globals.insert(x);
+            } else if !locals.contains(&x) {
+                locals.push(x);
+            }
+        }
+
+        Ok(SyntaxRule {
+            params: names,
+            template,
+            locals,
+            globals,
+        })
+    }
+
+    /// The template with `args` in place of the parameters and fresh names for its locals
+    fn instantiate(&self, ctx: &Ctx, use_site: &Sexpr, args: &[Sexpr]) -> Result<Sexpr> {
+        if args.len() != self.params.len() {
+            return Err(Error::WrongArity(Core::from(use_site)));
+        }
+
+        let mut used = &ctx.names_only() | &symbols(use_site);
+        used.extend(symbols(&self.template));
+        let mut renaming: HashMap<_, _> = self
+            .params
+            .iter()
+            .cloned()
+            .zip(args.iter().cloned())
+            .collect();
+        for x in &self.locals {
+            let y = freshen(&used, x);
+            used.insert(y.clone());
+            renaming.insert(x.clone(), Sexpr::Symbol(y));
+        }
+        Ok(substitute(&self.template, &renaming))
+    }
+}
+
+/// Expand the uses of macros in `expr`, including the uses that an expansion produces
+pub fn expand(ctx: &Ctx, expr: &Sexpr) -> Result<Sexpr> {
+    let globals = ctx
+        .syntax_rules()
+        .iter()
+        .flat_map(|rule| rule.globals.iter().cloned())
+        .collect();
+    Expansion { ctx, globals }.expand(expr)
+}
+
+struct Expansion<'a> {
+    ctx: &'a Ctx,
+    /// The globals that templates refer to, which variables must not capture
+    globals: HashSet<Symbol>,
+}
+
+impl Expansion<'_> {
+    fn expand(&self, expr: &Sexpr) -> Result<Sexpr> {
+        let items = match expr {
+            Sexpr::List(items) => items,
+            _ => return Ok(expr.clone()),
+        };
+
+        match &items[..] {
+            [Sexpr::Symbol(q), _] if q == &"quote" => Ok(expr.clone()),
+
+            // a template is expanded where it is used, not where it is defined
+            [Sexpr::Symbol(d), ..] if d == &"define-syntax" => Ok(expr.clone()),
+
+            [Sexpr::Symbol(m), args @ ..] => match self.ctx.syntax_rule(m) {
+                Some(rule) => self.expand(&rule.instantiate(self.ctx, expr, args)?),
+                None => self.expand_items(&self.rename_binders(expr, items)),
+            },
+
+            _ => self.expand_items(items),
+        }
+    }
+
+    fn expand_items(&self, items: &[Sexpr]) -> Result<Sexpr> {
+        Ok(Sexpr::List(
+            items
+                .iter()
+                .map(|e| self.expand(e))
+                .collect::<Result<_>>()?,
+        ))
+    }
+
+    /// Rename the variables that `expr` binds and that have the name of a global
+    fn rename_binders(&self, expr: &Sexpr, items: &[Sexpr]) -> Vec<Sexpr> {
+        let mut used = &self.ctx.names_only() | &symbols(expr);
+        used.extend(self.globals.iter().cloned());
+        let mut renaming = HashMap::new();
+        let mut rename = |x: &Sexpr, renaming: &mut HashMap<Symbol, Sexpr>| match x {
+            Sexpr::Symbol(x) if self.globals.contains(x) => {
+                let y = freshen(&used, x);
+                used.insert(y.clone());
+                renaming.insert(x.clone(), Sexpr::Symbol(y.clone()));
+                Sexpr::Symbol(y)
+            }
+            _ => x.clone(),
+        };
+
+        match items {
+            // the binders' scope is the types of the later binders and the body
+            [b @ Sexpr::Symbol(s), Sexpr::List(binders), body]
+                if ["λ", "lambda", "Π", "Pi", "∏", "∀", "Σ", "Sigma", "let*"]
+                    .contains(&s.name()) =>
+            {
+                let binders = binders
+                    .iter()
+                    .map(|binder| match binder {
+                        Sexpr::List(xt) if xt.len() == 2 => {
+                            let t = substitute(&xt[1], &renaming);
+                            Sexpr::List(vec![rename(&xt[0], &mut renaming), t])
+                        }
+                        x => rename(x, &mut renaming),
+                    })
+                    .collect();
+                vec![b.clone(), Sexpr::List(binders), substitute(body, &renaming)]
+            }
+
+            // the binders' scope is just the body
+            [l @ Sexpr::Symbol(s), Sexpr::List(bindings), body] if s == &"let" => {
+                let bindings = bindings
+                    .iter()
+                    .map(|binding| match binding {
+                        Sexpr::List(xe) if xe.len() == 2 => {
+                            Sexpr::List(vec![rename(&xe[0], &mut renaming), xe[1].clone()])
+                        }
+                        _ => binding.clone(),
+                    })
+                    .collect();
+                vec![
+                    l.clone(),
+                    Sexpr::List(bindings),
+                    substitute(body, &renaming),
+                ]
+            }
+
+            // each clause binds the fields of its constructor in its body
+            [m @ Sexpr::Symbol(s), target, clauses @ ..] if s == &"match" => {
+                let mut out = vec![m.clone(), target.clone()];
+                for clause in clauses {
+                    out.push(match clause {
+                        Sexpr::List(pb) if pb.len() == 2 => match &pb[0] {
+                            Sexpr::List(pattern) if !pattern.is_empty() => {
+                                let mut renaming = HashMap::new();
+                                let mut pattern_out = vec![pattern[0].clone()];
+                                for x in &pattern[1..] {
+                                    pattern_out.push(rename(x, &mut renaming));
+                                }
+                                Sexpr::List(vec![
+                                    Sexpr::List(pattern_out),
+                                    substitute(&pb[1], &renaming),
+                                ])
+                            }
+                            _ => clause.clone(),
+                        },
+                        _ => clause.clone(),
+                    });
+                }
+                out
+            }
+
+            _ => items.to_vec(),
+        }
+    }
+}
+
+fn substitute(expr: &Sexpr, renaming: &HashMap<Symbol, Sexpr>) -> Sexpr {
+    match expr {
+        Sexpr::Symbol(x) => renaming.get(x).cloned().unwrap_or_else(|| expr.clone()),
+        Sexpr::List(items) if is_quote(items) => expr.clone(),
+        Sexpr::List(items) => Sexpr::List(items.iter().map(|e| substitute(e, renaming)).collect()),
+        _ => expr.clone(),
+    }
+}
+
+/// The names in `expr`, other than quoted atoms
+fn symbols(expr: &Sexpr) -> HashSet<Symbol> {
+    symbols_in_order(expr).into_iter().collect()
+}
+
+fn symbols_in_order(expr: &Sexpr) -> Vec<Symbol> {
+    match expr {
+        Sexpr::Symbol(s) => vec![s.clone()],
+        Sexpr::List(items) if is_quote(items) => vec![],
+        Sexpr::List(items) => items.iter().flat_map(symbols_in_order).collect(),
+        _ => vec![],
+    }
+}
+
+fn is_quote(items: &[Sexpr]) -> bool {
+    matches!(items, [q, _] if q == "quote")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::basics::{Core, Ctx};
+    use crate::errors::Error;
+    use crate::rep::eval_normalize;
+
+    fn define_le(ctx: &mut Ctx) {
+        eval_normalize(
+            ctx,
+            "(define-syntax (<= a b)
+               (Σ ((k Nat)) (= Nat (iter-Nat k a (λ (n) (add1 n))) b)))",
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn macros_are_expanded_before_elaboration() {
+        let mut ctx = Ctx::new();
+        define_le(&mut ctx);
+        assert_eq!(
+            eval_normalize(&mut ctx, "(the (<= 1 3) (cons 2 (same 3)))").map(|e| e.is_some()),
+            Ok(true)
+        );
+        assert!(eval_normalize(&mut ctx, "(the (<= 3 1) (cons 2 (same 1)))").is_err());
+    }
+
+    #[test]
+    fn macros_do_not_capture_the_names_in_their_arguments() {
+        let mut ctx = Ctx::new();
+        define_le(&mut ctx);
+        eval_normalize(&mut ctx, "(claim k Nat)").unwrap();
+        eval_normalize(&mut ctx, "(define k 1)").unwrap();
+        // without renaming the template's k, this would claim that k + k is 3
+        assert_eq!(
+            eval_normalize(&mut ctx, "(the (<= k 3) (cons 2 (same 3)))").map(|e| e.is_some()),
+            Ok(true)
+        );
+    }
+
+    #[test]
+    fn the_names_in_templates_are_not_captured_by_their_uses() {
+        let mut ctx = Ctx::new();
+        eval_normalize(&mut ctx, "(claim f (-> Nat Nat))").unwrap();
+        eval_normalize(&mut ctx, "(define f (λ (n) (add1 n)))").unwrap();
+        eval_normalize(&mut ctx, "(define-syntax (app-f x) (f x))").unwrap();
+        // without renaming the λ's f, the template's f would be the identity
+        assert_eq!(
+            eval_normalize(
+                &mut ctx,
+                "((the (-> (-> Nat Nat) Nat) (λ (f) (app-f 1))) (λ (n) n))"
+            ),
+            Ok(Some("(the Nat 2)".parse().unwrap()))
+        );
+        assert_eq!(
+            eval_normalize(
+                &mut ctx,
+                "((the (-> (-> Nat Nat) Nat) (λ (f) (app-f (f 5)))) (λ (n) n))"
+            ),
+            Ok(Some("(the Nat 6)".parse().unwrap()))
+        );
+    }
+
+    #[test]
+    fn macros_may_use_other_macros() {
+        let mut ctx = Ctx::new();
+        define_le(&mut ctx);
+        eval_normalize(&mut ctx, "(define-syntax (< a b) (<= (add1 a) b))").unwrap();
+        eval_normalize(&mut ctx, "(claim two<three (< 2 3))").unwrap();
+        eval_normalize(&mut ctx, "(define two<three (cons 0 (same 3)))").unwrap();
+        assert_eq!(
+            eval_normalize(&mut ctx, "(< 2)"),
+            Err(Error::WrongArity("(< 2)".parse::<Core>().unwrap()).to_string())
+        );
+    }
+
+    #[test]
+    fn built_in_forms_cannot_be_redefined() {
+        let mut ctx = Ctx::new();
+        assert_eq!(
+            eval_normalize(&mut ctx, "(define-syntax (the t e) e)"),
+            Err(Error::NotAVarName("the".into()).to_string())
+        );
+    }
+}
@@ -160,8 +160,15 @@ fn which_nat_stays_unresolved_if_target_is_neutral() {
 }
 
 #[test]
-fn u_has_no_type() {
-    assert_eq!(rep(&CTX, &universe()), Err(Error::UhasNoType))
+fn u_is_in_the_next_universe() {
+    assert_eq!(
+        rep(&CTX, &universe()),
+        Ok(the(universe_level(1), universe()))
+    );
+    assert_eq!(
+        rep(&CTX, &"U1".parse().unwrap()),
+        Ok(the(universe_level(2), universe_level(1)))
+    );
 }
 
 #[test]
@@ -254,10 +261,57 @@ fn function_type_expands_to_pi() {
 fn cant_have_function_map_from_u_to_u() {
     assert_eq!(
         rep(&CTX, &"(the U (-> U U))".parse().unwrap()),
-        Err(Error::UhasNoType)
+        Err(Error::WrongType(universe_level(1), universe()))
     )
 }
 
+#[test]
+fn function_map_from_u_to_u_is_a_u1() {
+    assert_eq!(
+        rep(&CTX, &"(the U1 (-> U U))".parse().unwrap()),
+        Ok(the(universe_level(1), pi("x", universe(), universe())))
+    )
+}
+
+#[test]
+fn universes_are_cumulative() {
+    assert_eq!(
+        rep(&CTX, &"(the U2 Nat)".parse().unwrap()),
+        Ok(the(universe_level(2), nat()))
+    );
+    assert_eq!(
+        rep(&CTX, &"(the U1 (Pair Nat U))".parse().unwrap()),
+        Ok(the(universe_level(1), sigma("a", nat(), universe())))
+    );
+    assert_eq!(
+        rep(&CTX, &"(the U (Pair Nat U))".parse().unwrap()),
+        Err(Error::WrongType(universe_level(1), universe()))
+    );
+}
+
+#[test]
+fn type_level_functions_over_families_of_types() {
+    let ctx = CTX
+        .claim("Endo", "U1".parse().unwrap())
+        .unwrap()
+        .define("Endo", "(-> U U)".parse().unwrap())
+        .unwrap()
+        .claim("Fam", "(-> U U1)".parse().unwrap())
+        .unwrap()
+        .define("Fam", "(λ (A) (-> A U))".parse().unwrap())
+        .unwrap()
+        .claim("twice-U", "(-> Endo Endo)".parse().unwrap())
+        .unwrap()
+        .define("twice-U", "(λ (f A) (f (f A)))".parse().unwrap())
+        .unwrap();
+
+    assert_eq!(
+        rep(&ctx, &"(twice-U (λ (A) (List A)) Nat)".parse().unwrap()),
+        Ok(the(universe(), list(list(nat()))))
+    );
+    assert!(rep(&ctx, &"(the U1 (Fam Nat))".parse().unwrap()).is_ok());
+}
+
 #[test]
 fn function_type_expands_to_pis() {
     assert_eq!(
@@ -474,7 +528,10 @@ fn argument_to_cdr_must_be_a_pair() {
 
 #[test]
 fn can_evaluate_u() {
-    assert_eq!(norm(&CTX, &"U".parse().unwrap()), Ok(universe()))
+    assert_eq!(
+        norm(&CTX, &"U".parse().unwrap()),
+        Ok(the(universe_level(1), universe()))
+    )
 }
 
 #[test]
@@ -485,6 +542,46 @@ fn can_use_todos() {
     )
 }
 
+#[test]
+fn anonymous_todos_are_named_by_position() {
+    assert_eq!(
+        norm(
+            &CTX,
+            &"(the (Pair Nat Atom) (cons TODO TODO))".parse().unwrap()
+        ),
+        Ok(the(
+            sigma("x", nat(), atom()),
+            cons(
+                annotated_todo("TODO-1", nat()),
+                annotated_todo("TODO-2", atom())
+            )
+        ))
+    );
+    assert_eq!(
+        norm(&CTX, &"(the Atom 'TODO)".parse().unwrap()),
+        Ok(the(atom(), quote("TODO")))
+    );
+}
+
+#[test]
+fn definitions_with_todos_can_be_used() {
+    let ctx = &mut CTX.clone();
+    eval_normalize(ctx, "(claim step (-> Nat Nat))").unwrap();
+    eval_normalize(ctx, "(define step (λ (n) (add1 TODO)))").unwrap();
+    eval_normalize(ctx, "(claim twice (-> Nat Nat))").unwrap();
+    eval_normalize(ctx, "(define twice (λ (n) (step (step n))))").unwrap();
+
+    assert_eq!(
+        eval_normalize(ctx, "(twice 0)")
+            .unwrap()
+            .unwrap()
+            .to_string(),
+        "(the Nat (add1 (TODO TODO-step-1: Nat)))"
+    );
+    assert!(eval_normalize(ctx, "(the (= Nat (twice 0) (step 1)) (same (twice 0)))").is_ok());
+    assert!(eval_normalize(ctx, "(the (= Nat (twice 0) (add1 TODO)) (same (twice 0)))").is_err());
+}
+
 #[test]
 fn simple_ind_vec() {
     assert_eq!(
@@ -536,6 +633,700 @@ fn neutral12_ind_vec() {
     )
 }
 
+#[test]
+fn let_bound_names_are_transparent() {
+    assert_eq!(
+        norm(&CTX, &"(let ((x (the Nat 2))) (add1 x))".parse().unwrap()),
+        Ok(the(nat(), the_nat(3)))
+    );
+    assert_eq!(
+        norm(&CTX, &"(let ((A Nat)) (the A 3))".parse().unwrap()),
+        Ok(the(nat(), the_nat(3)))
+    );
+}
+
+#[test]
+fn let_bindings_are_parallel_and_let_star_bindings_are_sequential() {
+    assert_eq!(
+        norm(
+            &CTX,
+            &"(the (-> Nat Nat) (λ (x) (let ((x (the Nat 5)) (y x)) y)))"
+                .parse()
+                .unwrap()
+        ),
+        Ok(the(pi("x", nat(), nat()), lambda("x", refer("x"))))
+    );
+    assert_eq!(
+        norm(
+            &CTX,
+            &"(the (-> Nat Nat) (λ (x) (let* ((x (the Nat 5)) (y x)) y)))"
+                .parse()
+                .unwrap()
+        ),
+        Ok(the(pi("x", nat(), nat()), lambda("x", the_nat(5))))
+    );
+}
+
+#[test]
+fn let_keeps_helpers_local() {
+    let ctx = &mut CTX.clone();
+    eval_normalize(ctx, "(claim double (-> Nat Nat))").unwrap();
+    eval_normalize(
+        ctx,
+        "(define double
+           (λ (n)
+             (let ((step (the (-> Nat Nat) (λ (k) (add1 (add1 k))))))
+               (iter-Nat n 0 step))))",
+    )
+    .unwrap();
+
+    assert_eq!(
+        eval_normalize(ctx, "(double 3)"),
+        Ok(Some(the(nat(), the_nat(6))))
+    );
+    assert_eq!(
+        eval_normalize(ctx, "step"),
+        Err(Error::UnknownVariable("step".into()).to_string())
+    );
+}
+
+#[test]
+fn let_bound_expressions_must_synthesize() {
+    assert_eq!(
+        norm(&CTX, &"(let ((f (λ (x) x))) f)".parse().unwrap()),
+        Err(Error::CantDetermineType(lambda_star(
+            vec!["x".into()],
+            refer("x")
+        )))
+    );
+}
+
+#[test]
+fn lambdas_with_typed_binders_synthesize_pi_types() {
+    assert_eq!(
+        norm(&CTX, &"(λ ((n Nat)) (add1 n))".parse().unwrap()),
+        Ok(the(pi("n", nat(), nat()), lambda("n", add1(refer("n")))))
+    );
+    assert_eq!(
+        norm(&CTX, &"(λ ((n Nat) a) n)".parse().unwrap()),
+        Err(Error::CantDetermineType(lambda("a", refer("n"))))
+    );
+    assert_eq!(
+        norm(
+            &CTX,
+            &"((λ ((n Nat) (b Atom)) (cons b n)) 2 'x)".parse().unwrap()
+        ),
+        Ok(the(sigma("a", atom(), nat()), cons(quote("x"), the_nat(2))))
+    );
+    assert_eq!(
+        norm(
+            &CTX,
+            &"(the (-> Atom Atom) (λ ((n Nat)) 'x))".parse().unwrap()
+        ),
+        Err(Error::WrongType(atom(), nat()))
+    );
+}
+
+#[test]
+fn same_pairs_and_lists_synthesize_when_their_parts_do() {
+    assert_eq!(
+        norm(&CTX, &"(same 'x)".parse().unwrap()),
+        Ok(the(equal(atom(), quote("x"), quote("x")), same(quote("x"))))
+    );
+    assert_eq!(
+        norm(&CTX, &"(cons 'x (same 1))".parse().unwrap()),
+        Ok(the(
+            sigma("a", atom(), equal(nat(), the_nat(1), the_nat(1))),
+            cons(quote("x"), same(the_nat(1)))
+        ))
+    );
+    assert_eq!(
+        norm(&CTX, &"(:: 'x (:: 'y nil))".parse().unwrap()),
+        Ok(the(
+            list(atom()),
+            list_cons(quote("x"), list_cons(quote("y"), nil()))
+        ))
+    );
+    assert!(norm(
+        &CTX,
+        &"(the (List (-> Nat Nat)) (:: (λ (n) n) nil))"
+            .parse()
+            .unwrap()
+    )
+    .is_ok());
+}
+
+#[test]
+fn opaque_definitions_do_not_unfold() {
+    let ctx = &mut CTX.clone();
+    eval_normalize(ctx, "(claim two=two (= Nat 2 2))").unwrap();
+    eval_normalize(ctx, "(define-opaque two=two (same 2))").unwrap();
+    eval_normalize(ctx, "(claim two Nat)").unwrap();
+    eval_normalize(ctx, "(define-opaque two 2)").unwrap();
+
+    assert_eq!(
+        eval_normalize(ctx, "two=two"),
+        Ok(Some(the(
+            equal(nat(), the_nat(2), the_nat(2)),
+            refer("two=two")
+        )))
+    );
+    assert_eq!(
+        eval_normalize(ctx, "(the (= Nat two 2) (same 2))"),
+        Err(Error::NotTheSame(nat(), refer("two"), the_nat(2)).to_string())
+    );
+    assert!(eval_normalize(ctx, "(the (= Nat two two) (same two))").is_ok());
+}
+
+#[test]
+fn opaque_definitions_are_checked() {
+    let ctx = &mut CTX.clone();
+    eval_normalize(ctx, "(claim two Nat)").unwrap();
+    assert_eq!(
+        eval_normalize(ctx, "(define-opaque two 'two)"),
+        Err(Error::WrongType(atom(), nat()).to_string())
+    );
+    eval_normalize(ctx, "(define-opaque two 2)").unwrap();
+    assert_eq!(
+        eval_normalize(ctx, "(define-opaque two 2)"),
+        Err(Error::DefinedName("two".into()).to_string())
+    );
+}
+
+#[test]
+fn postulates_are_neutral_constants() {
+    let ctx = &mut CTX.clone();
+    eval_normalize(ctx, "(postulate n Nat)").unwrap();
+    assert_eq!(
+        eval_normalize(ctx, "(add1 n)"),
+        Ok(Some(the(nat(), add1(refer("n")))))
+    );
+    assert_eq!(
+        eval_normalize(ctx, "(postulate n Atom)"),
+        Err(Error::DefinedName("n".into()).to_string())
+    );
+    assert_eq!(
+        eval_normalize(ctx, "(claim n Nat)"),
+        Err(Error::DefinedName("n".into()).to_string())
+    );
+}
+
+#[test]
+fn postulates_used_by_definitions_are_found_transitively() {
+    let ctx = &mut CTX.clone();
+    eval_normalize(ctx, "(postulate lem (Π ((X U)) (Either X (-> X Absurd))))").unwrap();
+    eval_normalize(ctx, "(postulate k Nat)").unwrap();
+    eval_normalize(ctx, "(claim nat-lem (Either Nat (-> Nat Absurd)))").unwrap();
+    eval_normalize(ctx, "(define-opaque nat-lem (lem Nat))").unwrap();
+    eval_normalize(
+        ctx,
+        "(claim uses-both (Pair (Either Nat (-> Nat Absurd)) Nat))",
+    )
+    .unwrap();
+    eval_normalize(ctx, "(define uses-both (cons nat-lem (add1 k)))").unwrap();
+    eval_normalize(ctx, "(claim axiom-free (= Nat 2 2))").unwrap();
+    eval_normalize(ctx, "(define axiom-free (same 2))").unwrap();
+
+    let atoms = |xs: &[&str]| {
+        xs.iter()
+            .rev()
+            .fold(nil(), |es, x| list_cons(quote(*x), es))
+    };
+    assert_eq!(
+        eval_normalize(ctx, "(postulates uses-both)"),
+        Ok(Some(the(list(atom()), atoms(&["k", "lem"]))))
+    );
+    assert_eq!(
+        eval_normalize(ctx, "(postulates nat-lem)"),
+        Ok(Some(the(list(atom()), atoms(&["lem"]))))
+    );
+    assert_eq!(
+        eval_normalize(ctx, "(postulates axiom-free)"),
+        Ok(Some(the(list(atom()), atoms(&[]))))
+    );
+    assert_eq!(
+        eval_normalize(ctx, "(postulates nothing)"),
+        Err(Error::UnknownVariable("nothing".into()).to_string())
+    );
+}
+
+#[test]
+fn enum_values_are_their_labels() {
+    let rgb = || enumeration(vec!["r".into(), "g".into(), "b".into()]);
+    assert_eq!(
+        norm(&CTX, &"(the (Enum 'r 'g 'b) 'g)".parse().unwrap()),
+        Ok(the(rgb(), quote("g")))
+    );
+    assert_eq!(
+        norm(&CTX, &"(the (Enum 'r 'g 'b) 'y)".parse().unwrap()),
+        Err(Error::NotALabelOf("y".into(), rgb()))
+    );
+    assert_eq!(
+        norm(
+            &CTX,
+            &"(the (= (Enum 'r 'g 'b) 'r 'g) (same 'r))".parse().unwrap()
+        ),
+        Err(Error::NotTheSame(rgb(), quote("g"), quote("r")))
+    );
+    assert_eq!(
+        norm(&CTX, &"(Enum 'r 'g 'r)".parse().unwrap()),
+        Err(Error::DuplicateLabel("r".into()))
+    );
+}
+
+#[test]
+fn ind_enum_picks_the_case_of_the_target() {
+    let ctx = &mut CTX.clone();
+    eval_normalize(ctx, "(claim next (-> (Enum 'r 'g 'b) (Enum 'r 'g 'b)))").unwrap();
+    eval_normalize(
+        ctx,
+        "(define next (λ (c) (ind-Enum c (λ (c) (Enum 'r 'g 'b)) 'g 'b 'r)))",
+    )
+    .unwrap();
+    eval_normalize(
+        ctx,
+        "(claim thrice-next (Π ((c (Enum 'r 'g 'b))) (= (Enum 'r 'g 'b) (next (next (next c))) c)))",
+    )
+    .unwrap();
+    eval_normalize(
+        ctx,
+        "(define thrice-next
+           (λ (c) (ind-Enum c (λ (c) (= (Enum 'r 'g 'b) (next (next (next c))) c))
+                    (same 'r) (same 'g) (same 'b))))",
+    )
+    .unwrap();
+
+    assert_eq!(
+        eval_normalize(ctx, "(next 'b)"),
+        Ok(Some(the(
+            enumeration(vec!["r".into(), "g".into(), "b".into()]),
+            quote("r")
+        )))
+    );
+    assert_eq!(
+        eval_normalize(ctx, "(ind-Enum (the (Enum 'r 'g 'b) 'g) (λ (c) Nat) 0)"),
+        Err(Error::WrongArity(ind_enum(
+            "(the (Enum 'r 'g 'b) 'g)".parse().unwrap(),
+            "(λ (c) Nat)".parse().unwrap(),
+            vec![the_nat(0)]
+        ))
+        .to_string())
+    );
+    assert_eq!(
+        eval_normalize(
+            ctx,
+            "(the (-> (Enum 'r 'g 'b) (Enum 'r 'g 'b)) (λ (c) (next c)))"
+        )
+        .unwrap()
+        .unwrap()
+        .to_string(),
+        "(the (Π ((x (Enum 'r 'g 'b))) (Enum 'r 'g 'b)) \
+         (λ (c) (ind-Enum (the (Enum 'r 'g 'b) c) (λ (c₁) (Enum 'r 'g 'b)) 'g 'b 'r)))"
+    );
+}
+
+#[test]
+fn streams_are_observed_one_step_at_a_time() {
+    let ctx = &mut CTX.clone();
+    eval_normalize(ctx, "(claim nats (Stream Nat))").unwrap();
+    eval_normalize(
+        ctx,
+        "(define nats (corec-Stream 0 (λ (n) (cons n (add1 n)))))",
+    )
+    .unwrap();
+
+    assert_eq!(
+        eval_normalize(ctx, "(stream-head (stream-tail (stream-tail nats)))"),
+        Ok(Some(the(nat(), the_nat(2))))
+    );
+    assert_eq!(
+        eval_normalize(ctx, "(stream-tail nats)")
+            .unwrap()
+            .unwrap()
+            .to_string(),
+        "(the (Stream Nat) (corec-Stream (the Nat 1) \
+         (λ (n) (cons n (add1 n)))))"
+    );
+    assert!(eval_normalize(
+        ctx,
+        "(the (= (Stream Nat) (stream-tail nats) (stream-tail nats)) (same (stream-tail nats)))"
+    )
+    .is_ok());
+    assert_eq!(
+        eval_normalize(
+            ctx,
+            "(the (Stream Atom) (corec-Stream 0 (λ (n) (cons n n))))"
+        ),
+        Err(Error::WrongType(nat(), atom()).to_string())
+    );
+}
+
+#[test]
+fn observations_of_neutral_streams_are_neutral() {
+    assert_eq!(
+        norm(
+            &CTX,
+            &"(the (-> (Stream Atom) Atom) (λ (s) (stream-head (stream-tail s))))"
+                .parse()
+                .unwrap()
+        ),
+        Ok(the(
+            pi("x", stream(atom()), atom()),
+            lambda("s", stream_head(stream_tail(refer("s"))))
+        ))
+    );
+}
+
+#[test]
+fn records_have_named_fields() {
+    let ctx = &mut CTX.clone();
+    eval_normalize(ctx, "(record Point ((x Nat) (y Nat)))").unwrap();
+    eval_normalize(ctx, "(claim origin Point)").unwrap();
+    eval_normalize(ctx, "(define origin (make Point (y 0) (x 0)))").unwrap();
+
+    assert_eq!(
+        eval_normalize(ctx, "(make Point (x 1) (y (field origin y)))")
+            .unwrap()
+            .unwrap()
+            .to_string(),
+        "(the Point (make Point (x 1) (y 0)))"
+    );
+    assert_eq!(
+        eval_normalize(ctx, "(field (make Point (x 1) (y 2)) y)"),
+        Ok(Some(the(nat(), the_nat(2))))
+    );
+    assert_eq!(
+        eval_normalize(ctx, "(the (-> Point Nat) (λ (p) (field p x)))")
+            .unwrap()
+            .unwrap()
+            .to_string(),
+        "(the (Π ((x Point)) Nat) (λ (p) (field p x)))"
+    );
+    assert_eq!(
+        eval_normalize(
+            ctx,
+            "(the (-> Point Point) (λ (p) (make Point (x (field p y)) (y (field p x)))))"
+        )
+        .unwrap()
+        .unwrap()
+        .to_string(),
+        "(the (Π ((x Point)) Point) (λ (p) (make Point (x (field p y)) (y (field p x)))))"
+    );
+    assert_eq!(
+        eval_normalize(ctx, "(make Point (x 1))"),
+        Err(Error::MissingField("y".into()).to_string())
+    );
+    assert_eq!(
+        eval_normalize(ctx, "(field origin z)"),
+        Err(Error::NotAFieldOf("z".into(), refer("Point")).to_string())
+    );
+    assert_eq!(
+        eval_normalize(ctx, "(field (cons 1 2) x)"),
+        Err(Error::NotARecordType(sigma("a", nat(), nat())).to_string())
+    );
+    assert!(eval_normalize(ctx, "(the Point (cons 1 2))").is_ok());
+}
+
+#[test]
+fn records_are_their_sigma_layouts() {
+    let ctx = &mut CTX.clone();
+    eval_normalize(ctx, "(record Point ((x Nat) (y Nat)))").unwrap();
+    assert_eq!(
+        eval_normalize(ctx, "(the (Pair Nat Nat) (make Point (x 1) (y 2)))")
+            .unwrap()
+            .unwrap()
+            .to_string(),
+        "(the (Σ ((x Nat)) Nat) (cons 1 2))"
+    );
+    assert_eq!(
+        eval_normalize(
+            ctx,
+            "(the (-> (Pair Nat Nat) Nat) (the (-> Point Nat) (λ (p) (field p x))))"
+        )
+        .unwrap()
+        .unwrap()
+        .to_string(),
+        "(the (Π ((x (Σ ((x Nat)) Nat))) Nat) (λ (p) (car p)))"
+    );
+    assert_eq!(
+        eval_normalize(
+            ctx,
+            "(the (Π ((p Point)) (= Nat (field p y) (cdr p))) (λ (p) (same (cdr p))))"
+        )
+        .unwrap()
+        .unwrap()
+        .to_string(),
+        "(the (Π ((p Point)) (= Nat (field p y) (field p y))) (λ (p) (same (field p y))))"
+    );
+}
+
+#[test]
+fn record_fields_may_depend_on_earlier_fields() {
+    let ctx = &mut CTX.clone();
+    eval_normalize(
+        ctx,
+        "(record Bag ((size Nat) (items (Vec Atom size)) (label Atom)))",
+    )
+    .unwrap();
+    assert_eq!(
+        eval_normalize(
+            ctx,
+            "(field (make Bag (size 1) (items (vec:: 'a vecnil)) (label 'b)) items)"
+        )
+        .unwrap()
+        .unwrap()
+        .to_string(),
+        "(the (Vec Atom 1) (vec:: 'a vecnil))"
+    );
+    assert_eq!(
+        eval_normalize(ctx, "(make Bag (size 1) (items vecnil) (label 'b))"),
+        Err(Error::LengthNotZero(the_nat(1)).to_string())
+    );
+    assert_eq!(
+        eval_normalize(ctx, "(record Twice ((x Nat) (x Atom)))"),
+        Err(Error::DuplicateField("x".into()).to_string())
+    );
+}
+
+#[test]
+fn fields_of_neutral_records_are_read_back_by_name() {
+    let ctx = &mut CTX.clone();
+    eval_normalize(
+        ctx,
+        "(record Bag ((size Nat) (items (Vec Atom size)) (label Atom)))",
+    )
+    .unwrap();
+    assert_eq!(
+        eval_normalize(
+            ctx,
+            "(the (Π ((b Bag)) (Vec Atom (field b size))) (λ (b) (field b items)))"
+        )
+        .unwrap()
+        .unwrap()
+        .to_string(),
+        "(the (Π ((b Bag)) (Vec Atom (field b size))) (λ (b) (field b items)))"
+    );
+    assert_eq!(
+        eval_normalize(ctx, "(the (-> Bag Atom) (λ (b) (cdr (cdr b))))")
+            .unwrap()
+            .unwrap()
+            .to_string(),
+        "(the (Π ((x Bag)) Atom) (λ (b) (field b label)))"
+    );
+    // a record with a single field is just that field
+    eval_normalize(ctx, "(record One ((n Nat)))").unwrap();
+    assert_eq!(
+        eval_normalize(ctx, "(the (-> One Nat) (λ (o) (add1 (field o n))))")
+            .unwrap()
+            .unwrap()
+            .to_string(),
+        "(the (Π ((x One)) Nat) (λ (o) (add1 o)))"
+    );
+}
+
+#[test]
+fn closed_nats_have_a_single_normal_form() {
+    let ctx = &mut CTX.clone();
+    let expected = "(the (= Nat 3 3) (same 3))";
+    for src in [
+        "(the (= Nat 3 (add1 (add1 (add1 zero)))) (same 3))",
+        "(the (= Nat (add1 (add1 (add1 zero))) 3) (same (add1 2)))",
+    ] {
+        assert_eq!(
+            eval_normalize(ctx, src).unwrap().unwrap().to_string(),
+            expected
+        );
+    }
+    assert_eq!(
+        eval_normalize(ctx, "(the Nat zero)")
+            .unwrap()
+            .unwrap()
+            .to_string(),
+        "(the Nat 0)"
+    );
+    assert_eq!(
+        eval_normalize(ctx, "(the (-> Nat Nat) (λ (n) (add1 (add1 n))))")
+            .unwrap()
+            .unwrap()
+            .to_string(),
+        "(the (Π ((x Nat)) Nat) (λ (n) (add1 (add1 n))))"
+    );
+}
+
+#[test]
+fn numerals_are_the_same_as_chains_of_add1() {
+    assert_eq!(
+        norm(&CTX, &"(the Nat (add1 (add1 zero)))".parse().unwrap()),
+        Ok(the(nat(), the_nat(2)))
+    );
+    assert!(norm(
+        &CTX,
+        &"(the (= Nat 3 (add1 (add1 (add1 zero)))) (same (add1 2)))"
+            .parse()
+            .unwrap()
+    )
+    .is_ok());
+    assert_eq!(
+        norm(
+            &CTX,
+            &"(the (= Nat 2 (add1 zero)) (same 2))".parse().unwrap()
+        ),
+        Err(Error::NotTheSame(nat(), the_nat(1), the_nat(2)))
+    );
+    assert_eq!(
+        norm(&CTX, &"(which-Nat 5 0 (λ (n-1) n-1))".parse().unwrap()),
+        Ok(the(nat(), the_nat(4)))
+    );
+    assert_eq!(
+        norm(
+            &CTX,
+            &"(head (the (Vec Atom 2) (vec:: 'a (vec:: 'b vecnil))))"
+                .parse()
+                .unwrap()
+        ),
+        Ok(the(atom(), quote("a")))
+    );
+}
+
+#[test]
+fn arithmetic_on_numerals_stays_compact() {
+    let ctx = &mut CTX.clone();
+    eval_normalize(ctx, "(claim + (-> Nat Nat Nat))").unwrap();
+    eval_normalize(ctx, "(define + (λ (n j) (iter-Nat n j (λ (k) (add1 k)))))").unwrap();
+    eval_normalize(ctx, "(claim * (-> Nat Nat Nat))").unwrap();
+    eval_normalize(
+        ctx,
+        "(define * (λ (n j) (rec-Nat n 0 (λ (n-1 k) (+ j k)))))",
+    )
+    .unwrap();
+
+    assert_eq!(
+        eval_normalize(ctx, "(* 100 1000)"),
+        Ok(Some(the(nat(), the_nat(100_000))))
+    );
+    assert_eq!(
+        eval_normalize(ctx, "(+ 1 100000000000000000000)")
+            .unwrap()
+            .unwrap()
+            .to_string(),
+        "(the Nat 100000000000000000001)"
+    );
+}
+
+#[test]
+fn atom_equality_is_decided_on_quotes() {
+    let ctx = &mut CTX.clone();
+    assert_eq!(
+        eval_normalize(ctx, "(atom=? 'a 'a)")
+            .unwrap()
+            .unwrap()
+            .to_string(),
+        "(the (Either (= Atom 'a 'a) (Π ((x (= Atom 'a 'a))) Absurd)) (left (same 'a)))"
+    );
+    assert_eq!(
+        eval_normalize(ctx, "(atom=? 'a 'b)")
+            .unwrap()
+            .unwrap()
+            .to_string(),
+        "(the (Either (= Atom 'a 'b) (Π ((x (= Atom 'a 'b))) Absurd)) \
+         (right (atom-apart 'a 'b)))"
+    );
+    assert_eq!(
+        eval_normalize(ctx, "(atom-apart 'a 'a)"),
+        Err(Error::AtomsNotApart("a".into()).to_string())
+    );
+
+    // the decision can be branched on
+    eval_normalize(ctx, "(claim same-atom? (-> Atom Atom Nat))").unwrap();
+    eval_normalize(
+        ctx,
+        "(define same-atom?
+           (λ (a b) (ind-Either (atom=? a b) (λ (_) Nat) (λ (_) 1) (λ (_) 0))))",
+    )
+    .unwrap();
+    assert_eq!(
+        eval_normalize(ctx, "(same-atom? 'pea 'pea)"),
+        Ok(Some(the(nat(), the_nat(1))))
+    );
+    assert_eq!(
+        eval_normalize(ctx, "(same-atom? 'pea 'bean)"),
+        Ok(Some(the(nat(), the_nat(0))))
+    );
+}
+
+#[test]
+fn atom_equality_on_variables_is_neutral() {
+    assert_eq!(
+        norm(
+            &CTX,
+            &"(the (Π ((x Atom)) (Either (= Atom x 'a) (-> (= Atom x 'a) Absurd))) \
+               (λ (x) (atom=? x 'a)))"
+                .parse()
+                .unwrap()
+        )
+        .unwrap()
+        .to_string(),
+        "(the (Π ((x Atom)) (Either (= Atom x 'a) (Π ((x₁ (= Atom x 'a))) Absurd))) \
+         (λ (x) (atom=? x 'a)))"
+    );
+}
+
+#[test]
+fn integers_are_written_as_literals() {
+    let ctx = &mut CTX.clone();
+    assert_eq!(
+        eval_normalize(ctx, "-3").unwrap().unwrap().to_string(),
+        "(the Int -3)"
+    );
+    assert_eq!(
+        eval_normalize(ctx, "(the Int 5)")
+            .unwrap()
+            .unwrap()
+            .to_string(),
+        "(the Int 5)"
+    );
+    assert_eq!(
+        eval_normalize(ctx, "(negate-Int (pred-Int (succ-Int (pred-Int 0))))")
+            .unwrap()
+            .unwrap()
+            .to_string(),
+        "(the Int 1)"
+    );
+    assert_eq!(
+        eval_normalize(ctx, "(the Nat -1)"),
+        Err(Error::WrongType(int(), nat()).to_string())
+    );
+    assert!(eval_normalize(ctx, "(the (= Int -1 (pred-Int 0)) (same -1))").is_ok());
+}
+
+#[test]
+fn ind_int_counts_up_or_down_from_zero() {
+    let ctx = &mut CTX.clone();
+    eval_normalize(ctx, "(claim abs (-> Int Nat))").unwrap();
+    eval_normalize(
+        ctx,
+        "(define abs
+           (λ (i) (ind-Int i (λ (_) Nat) 0 (λ (_ n) (add1 n)) (λ (_ n) (add1 n)))))",
+    )
+    .unwrap();
+
+    assert_eq!(
+        eval_normalize(ctx, "(abs -3)"),
+        Ok(Some(the(nat(), the_nat(3))))
+    );
+    assert_eq!(
+        eval_normalize(ctx, "(abs 4)"),
+        Ok(Some(the(nat(), the_nat(4))))
+    );
+    assert_eq!(
+        eval_normalize(ctx, "(the (-> Int Int) (λ (i) (negate-Int (succ-Int i))))")
+            .unwrap()
+            .unwrap()
+            .to_string(),
+        "(the (Π ((x Int)) Int) (λ (i) (negate-Int (succ-Int i))))"
+    );
+}
+
 #[test]
 fn regression_chapter11() {
     let ctx = &mut CTX.clone();
@@ -1,18 +1,73 @@
 use crate::alpha::is_alpha_equiv;
-use crate::basics::{Ctx, Value};
+use crate::basics::{Closure, Ctx, Value};
 use crate::errors::{Error, Result};
 use crate::normalize::read_back;
+use crate::types::functions::Pi;
+use crate::types::pairs::Sigma;
+use crate::types::reference::NeutralVar;
+use crate::types::universe::Universe;
+use crate::types::values;
 
+/// Check that a value of type `given` can be used where `expected` is required.
+/// Apart from the types being the same, this allows for universe cumulativity.
 pub fn same_type(ctx: &Ctx, given: &Value, expected: &Value) -> Result<()> {
     let given_e = given.read_back_type(ctx)?;
     let expected_e = expected.read_back_type(ctx)?;
-    if is_alpha_equiv(&given_e, &expected_e) {
+    if is_alpha_equiv(&given_e, &expected_e) || is_cumulative(ctx, given, expected)? {
         Ok(())
     } else {
         Err(Error::WrongType(given_e, expected_e))
     }
 }
 
+/// `U` is contained in `U1`, which is contained in `U2`, and so on.
+/// This extends covariantly to the result types of Π and to both parts of Σ.
+fn is_cumulative(ctx: &Ctx, given: &Value, expected: &Value) -> Result<bool> {
+    if let (Some(Universe(i)), Some(Universe(j))) =
+        (given.try_as::<Universe>(), expected.try_as::<Universe>())
+    {
+        return Ok(i <= j);
+    }
+
+    if let (Some(p1), Some(p2)) = (
+        given.try_as::<Pi<Value, Closure>>(),
+        expected.try_as::<Pi<Value, Closure>>(),
+    ) {
+        if same_type(ctx, &p2.arg_type, &p1.arg_type).is_err() {
+            return Ok(false);
+        }
+        let x_hat = ctx.fresh(&p1.arg_name);
+        let ctx_hat = ctx.bind_free(x_hat.clone(), p2.arg_type.clone())?;
+        let x_val = values::neutral(p2.arg_type.clone(), NeutralVar(x_hat));
+        return Ok(same_type(
+            &ctx_hat,
+            &p1.res_type.val_of(x_val.clone()),
+            &p2.res_type.val_of(x_val),
+        )
+        .is_ok());
+    }
+
+    if let (Some(s1), Some(s2)) = (
+        given.try_as::<Sigma<Value, Closure>>(),
+        expected.try_as::<Sigma<Value, Closure>>(),
+    ) {
+        if same_type(ctx, &s1.car_type, &s2.car_type).is_err() {
+            return Ok(false);
+        }
+        let x_hat = ctx.fresh(&s1.arg_name);
+        let ctx_hat = ctx.bind_free(x_hat.clone(), s1.car_type.clone())?;
+        let x_val = values::neutral(s1.car_type.clone(), NeutralVar(x_hat));
+        return Ok(same_type(
+            &ctx_hat,
+            &s1.cdr_type.val_of(x_val.clone()),
+            &s2.cdr_type.val_of(x_val),
+        )
+        .is_ok());
+    }
+
+    Ok(false)
+}
+
 pub fn convert(ctx: &Ctx, tv: &Value, av: &Value, bv: &Value) -> Result<()> {
     let a = read_back(ctx, tv, av)?;
     let b = read_back(ctx, tv, bv)?;
@@ -3,7 +3,7 @@ use crate::errors::{Error, Result};
 use crate::normalize::val_in_ctx;
 use crate::symbol::Symbol;
 use crate::types::absurd::Absurd;
-use crate::types::{cores, values};
+use crate::types::{cores, synth_type_level};
 use std::collections::HashSet;
 
 #[derive(Debug, Clone, PartialEq)]
@@ -21,8 +21,8 @@ impl CoreInterface for The {
     }
 
     fn is_type(&self, ctx: &Ctx, r: &Renaming) -> Result<Core> {
-        match self.check(ctx, r, &values::universe()) {
-            Ok(t_out) => Ok(t_out),
+        match synth_type_level(ctx, r, self) {
+            Ok((t_out, _)) => Ok(t_out),
             Err(_) => Err(Error::NotAType(Core::new(self.clone()))),
         }
     }
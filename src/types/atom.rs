@@ -1,8 +1,13 @@
 use crate::alpha;
-use crate::basics::{Core, CoreInterface, Ctx, Env, Renaming, Value, ValueInterface};
+use crate::basics::{
+    Core, CoreInterface, Ctx, Env, NeutralInterface, Renaming, The, Value, ValueInterface,
+};
 use crate::errors::{Error, Result};
+use crate::normalize::{read_back, val_in_ctx};
 use crate::symbol::Symbol;
-use crate::types::values::quote;
+use crate::typechecker::same_type;
+use crate::types::enumeration::Enum;
+use crate::types::values::{later, quote};
 use crate::types::{cores, values};
 use std::any::Any;
 use std::collections::HashSet;
@@ -16,6 +21,23 @@ pub struct Atom;
 #[derive(Debug, Clone, PartialEq)]
 pub struct Quote(pub Symbol);
 
+/// `(atom=? a b)`: decides whether two atoms are the same, with evidence either way. Its type
+/// is `(Either (= Atom a b) (-> (= Atom a b) Absurd))`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AtomEq(pub Core, pub Core);
+
+/// `(atom-apart 'a 'b)`: the evidence that two different quotes are not the same, as a
+/// function from a proof that they are to `Absurd`. No such proof exists, so applying it never
+/// reduces, and it is a primitive rather than something that could be defined.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AtomApart(pub Symbol, pub Symbol);
+
+#[derive(Debug)]
+pub struct NeutralAtomEq(pub The, pub The);
+
+#[derive(Debug)]
+pub struct NeutralAtomApart(pub Symbol, pub Symbol);
+
 impl CoreInterface for Atom {
     impl_core_defaults!(
         _,
@@ -38,7 +60,7 @@ impl CoreInterface for Atom {
 }
 
 impl CoreInterface for Quote {
-    impl_core_defaults!((), as_any, same, occurring_names, no_type, check_by_synth);
+    impl_core_defaults!((), as_any, same, occurring_names, no_type);
 
     fn val_of(&self, _env: &Env) -> Value {
         values::quote(self.0.clone())
@@ -52,6 +74,20 @@ impl CoreInterface for Quote {
         }
     }
 
+    fn check(&self, ctx: &Ctx, r: &Renaming, tv: &Value) -> Result<Core> {
+        // an enumeration contains only its labels
+        if let Some(Enum(labels)) = tv.try_as::<Enum>() {
+            return if labels.contains(&self.0) {
+                Ok(cores::quote(self.0.clone()))
+            } else {
+                Err(Error::NotALabelOf(self.0.clone(), tv.read_back_type(ctx)?))
+            };
+        }
+        let (t_out, e_out) = self.synth(ctx, r)?;
+        same_type(ctx, &val_in_ctx(ctx, &t_out), tv)?;
+        Ok(e_out)
+    }
+
     fn alpha_equiv_aux(
         &self,
         other: &dyn CoreInterface,
@@ -71,6 +107,80 @@ impl CoreInterface for Quote {
     }
 }
 
+impl CoreInterface for AtomEq {
+    impl_core_defaults!(
+        (0, 1),
+        as_any,
+        same,
+        occurring_names,
+        alpha_equiv,
+        no_type,
+        check_by_synth,
+        (resugar: atom_eq)
+    );
+
+    fn val_of(&self, env: &Env) -> Value {
+        do_atom_eq(
+            later(env.clone(), self.0.clone()),
+            later(env.clone(), self.1.clone()),
+        )
+    }
+
+    fn synth(&self, ctx: &Ctx, r: &Renaming) -> Result<(Core, Core)> {
+        let a_out = self.0.check(ctx, r, &values::atom())?;
+        let b_out = self.1.check(ctx, r, &values::atom())?;
+        let equal = cores::equal(cores::atom(), a_out.clone(), b_out.clone());
+        Ok((
+            cores::either(equal.clone(), cores::pi("x", equal, cores::absurd())),
+            cores::atom_eq(a_out, b_out),
+        ))
+    }
+}
+
+impl CoreInterface for AtomApart {
+    impl_core_defaults!(_, as_any, same, no_type, check_by_synth);
+
+    fn occurring_names(&self) -> HashSet<Symbol> {
+        HashSet::new()
+    }
+
+    fn val_of(&self, _env: &Env) -> Value {
+        values::neutral(
+            apart_type(values::quote(self.0.clone()), values::quote(self.1.clone())),
+            NeutralAtomApart(self.0.clone(), self.1.clone()),
+        )
+    }
+
+    fn synth(&self, _ctx: &Ctx, _r: &Renaming) -> Result<(Core, Core)> {
+        if self.0 == self.1 {
+            return Err(Error::AtomsNotApart(self.0.clone()));
+        }
+        let equal = cores::equal(
+            cores::atom(),
+            cores::quote(self.0.clone()),
+            cores::quote(self.1.clone()),
+        );
+        Ok((
+            cores::pi("x", equal, cores::absurd()),
+            cores::atom_apart(self.0.clone(), self.1.clone()),
+        ))
+    }
+
+    fn alpha_equiv_aux(
+        &self,
+        other: &dyn CoreInterface,
+        _lvl: usize,
+        _b1: &alpha::Bindings,
+        _b2: &alpha::Bindings,
+    ) -> bool {
+        CoreInterface::same(self, other)
+    }
+
+    fn resugar(&self) -> (HashSet<Symbol>, Core) {
+        (HashSet::new(), Core::new(self.clone()))
+    }
+}
+
 impl ValueInterface for Atom {
     fn as_any(&self) -> &dyn Any {
         self
@@ -112,6 +222,13 @@ impl ValueInterface for Quote {
 }
 
 impl_sexpr_display!(Atom, "Atom");
+impl_sexpr_display!(AtomEq, ("atom=?", 0, 1));
+
+impl std::fmt::Display for AtomApart {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "(atom-apart '{} '{})", self.0.name(), self.1.name())
+    }
+}
 
 impl std::fmt::Display for Quote {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
@@ -119,6 +236,47 @@ impl std::fmt::Display for Quote {
     }
 }
 
+/// The type of evidence that the atoms `a` and `b` are not the same
+fn apart_type(a: Value, b: Value) -> Value {
+    pi_type!(
+        ((_x as "x", values::equal(values::atom(), a, b))),
+        values::absurd()
+    )
+}
+
+fn do_atom_eq(a: Value, b: Value) -> Value {
+    if let (Some(Quote(x)), Some(Quote(y))) = (a.try_as::<Quote>(), b.try_as::<Quote>()) {
+        return if x == y {
+            values::left(values::same(a))
+        } else {
+            values::right(cores::atom_apart(x.clone(), y.clone()).val_of(&Env::new()))
+        };
+    }
+
+    let equal = values::equal(values::atom(), a.clone(), b.clone());
+    let apart = apart_type(a.clone(), b.clone());
+    values::neutral(
+        values::either(equal, apart),
+        NeutralAtomEq(The(values::atom(), a), The(values::atom(), b)),
+    )
+}
+
+impl NeutralInterface for NeutralAtomEq {
+    fn read_back_neutral(&self, ctx: &Ctx) -> Result<Core> {
+        let NeutralAtomEq(The(at, a), The(bt, b)) = self;
+        Ok(cores::atom_eq(
+            read_back(ctx, at, a)?,
+            read_back(ctx, bt, b)?,
+        ))
+    }
+}
+
+impl NeutralInterface for NeutralAtomApart {
+    fn read_back_neutral(&self, _ctx: &Ctx) -> Result<Core> {
+        Ok(cores::atom_apart(self.0.clone(), self.1.clone()))
+    }
+}
+
 pub fn atom_is_ok(_: &Symbol) -> bool {
     true
 }
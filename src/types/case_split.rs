@@ -0,0 +1,121 @@
+use crate::basics::{Core, CoreInterface, Ctx, Env, Renaming, Value};
+use crate::errors::{Error, Result};
+use crate::normalize::read_back;
+use crate::symbol::Symbol;
+use crate::types::either::Either;
+use crate::types::lists::List;
+use crate::types::motive::{fresh, Eliminator};
+use crate::types::natural::Nat;
+use crate::types::vec::Vector;
+use crate::types::{cores, values};
+use std::collections::HashSet;
+use std::fmt::Formatter;
+
+/// Splitting the hole `hole` into cases on the variable `target`: it stands for the
+/// eliminator of the target's type, with its motive inferred from the hole's type and a new
+/// hole for each case. The new holes are named after the old one and their case, such as
+/// `h-zero` and `h-add1` for `h`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CaseSplit {
+    target: Symbol,
+    hole: Symbol,
+}
+
+impl CaseSplit {
+    pub fn new(target: Symbol, hole: Symbol) -> Self {
+        CaseSplit { target, hole }
+    }
+
+    fn case(&self, name: &str) -> Core {
+        cores::todo(format!("{}-{}", self.hole.name(), name).as_str())
+    }
+}
+
+impl CoreInterface for CaseSplit {
+    impl_core_defaults!(
+        (target, hole),
+        as_any,
+        same,
+        no_type,
+        no_synth,
+        no_alpha_equiv
+    );
+
+    fn occurring_names(&self) -> HashSet<Symbol> {
+        [self.target.clone()].into_iter().collect()
+    }
+
+    fn val_of(&self, _env: &Env) -> Value {
+        unimplemented!("evaluate the eliminator that a case split stands for instead")
+    }
+
+    fn check(&self, ctx: &Ctx, r: &Renaming, tv: &Value) -> Result<Core> {
+        let tgt_tv = ctx.var_type(&r.rename(&self.target))?;
+        let mut used = ctx.names_only();
+        let tgt = cores::refer(self.target.clone());
+
+        let split = if tgt_tv.try_as::<Nat>().is_some() {
+            let n_1 = fresh(&mut used, &format!("{}-1", self.target.name()));
+            let ih = fresh(&mut used, "ih");
+            cores::inferred_motive(
+                Eliminator::IndNat,
+                vec![
+                    tgt,
+                    self.case("zero"),
+                    Core::lambda_star(vec![n_1, ih], self.case("add1")),
+                ],
+            )
+        } else if tgt_tv.try_as::<List<Value>>().is_some() {
+            let e = fresh(&mut used, "e");
+            let es = fresh(&mut used, "es");
+            let ih = fresh(&mut used, "ih");
+            cores::inferred_motive(
+                Eliminator::IndList,
+                vec![
+                    tgt,
+                    self.case("nil"),
+                    Core::lambda_star(vec![e, es, ih], self.case("::")),
+                ],
+            )
+        } else if let Some(Vector(_, len)) = tgt_tv.try_as::<Vector<Value>>() {
+            let k = fresh(&mut used, "k");
+            let e = fresh(&mut used, "e");
+            let es = fresh(&mut used, "es");
+            let ih = fresh(&mut used, "ih");
+            cores::inferred_motive(
+                Eliminator::IndVec,
+                vec![
+                    cores::resolved(read_back(ctx, &values::nat(), len)?),
+                    tgt,
+                    self.case("vecnil"),
+                    Core::lambda_star(vec![k, e, es, ih], self.case("vec::")),
+                ],
+            )
+        } else if tgt_tv.try_as::<Either<Value>>().is_some() {
+            let l = fresh(&mut used, "l");
+            let r = fresh(&mut used, "r");
+            cores::inferred_motive(
+                Eliminator::IndEither,
+                vec![
+                    tgt,
+                    Core::lambda(l, self.case("left")),
+                    Core::lambda(r, self.case("right")),
+                ],
+            )
+        } else {
+            return Err(Error::CantMatchOn(tgt_tv.read_back_type(ctx)?));
+        };
+
+        split.check(ctx, r, tv)
+    }
+
+    fn resugar(&self) -> (HashSet<Symbol>, Core) {
+        unimplemented!("resugar the eliminator that a case split stands for instead")
+    }
+}
+
+impl std::fmt::Display for CaseSplit {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "(split {} {})", self.hole.name(), self.target.name())
+    }
+}
@@ -2,19 +2,32 @@ use crate::basics::Core;
 use crate::symbol::Symbol;
 use crate::types::absurd::{Absurd, IndAbsurd};
 use crate::types::annotation::The;
-use crate::types::atom::{Atom, Quote};
+use crate::types::atom::{Atom, AtomApart, AtomEq, Quote};
+use crate::types::case_split::CaseSplit;
 use crate::types::either::{Either, IndEither, Left, Right};
-use crate::types::equality::{Cong, Cong2, Equal, Replace, Same, Symm};
-use crate::types::functions::{App, AppStar, Fun, Lambda, LambdaStar, Pi, PiStar};
+use crate::types::enumeration::{Enum, IndEnum};
+use crate::types::equality::{Cong, Cong2, Equal, IndEq, Replace, Same, Symm, Trans};
+use crate::types::functions::{
+    App, AppStar, Fun, ImplicitApp, ImplicitLambda, ImplicitPi, Lambda, LambdaStar, Pi, PiStar,
+    TypedLambda,
+};
+use crate::types::integer::{IndInt, Int, IntLiteral, NegateInt, PredInt, SuccInt};
 use crate::types::invalid::Invalid;
+use crate::types::let_expr::Let;
 use crate::types::lists::{IndList, List, ListCons, Nil, RecList};
-use crate::types::natural::{Add1, IndNat, IterNat, Nat, RecNat, WhichNat, Zero};
+use crate::types::matching::{Clause, Match};
+use crate::types::motive::{Eliminator, InferredMotive, Resolved};
+use crate::types::natural::{Add1, IndNat, IterNat, Nat, Numeral, RecNat, WhichNat, Zero};
 use crate::types::pairs::{Car, Cdr, Cons, Pair, Sigma, SigmaStar};
+use crate::types::record::{Field, Make, Projection};
 use crate::types::reference::Ref;
+use crate::types::search::Search;
+use crate::types::stream::{CorecStream, Stream, StreamHead, StreamTail};
 use crate::types::todo::ToDo;
 use crate::types::trivial::{Sole, Trivial};
 use crate::types::universe::Universe;
 use crate::types::vec::{Head, IndVec, Tail, VecNil, Vector, VectorCons};
+use num_bigint::{BigInt, BigUint};
 
 pub fn invalid_syntax(s: &str) -> Core {
     Core::new(Invalid(s.into()))
@@ -25,7 +38,11 @@ pub fn the(typ: Core, exp: Core) -> Core {
 }
 
 pub fn universe() -> Core {
-    Core::new(Universe)
+    universe_level(0)
+}
+
+pub fn universe_level(level: usize) -> Core {
+    Core::new(Universe(level))
 }
 
 pub fn nat() -> Core {
@@ -41,11 +58,11 @@ pub fn add1(n: Core) -> Core {
 }
 
 pub fn the_nat(n: u64) -> Core {
-    let mut out = zero();
-    for _ in 0..n {
-        out = add1(out);
-    }
-    out
+    numeral(n.into())
+}
+
+pub fn numeral(n: BigUint) -> Core {
+    Core::new(Numeral(n))
 }
 
 pub fn which_nat(target: Core, base: Core, step: Core) -> Core {
@@ -76,6 +93,30 @@ pub fn ind_nat(target: Core, motive: Core, base: Core, step: Core) -> Core {
     Core::new(IndNat::new(target, motive, base, step))
 }
 
+pub fn int() -> Core {
+    Core::new(Int)
+}
+
+pub fn int_literal(i: BigInt) -> Core {
+    Core::new(IntLiteral(i))
+}
+
+pub fn succ_int(i: Core) -> Core {
+    Core::new(SuccInt(i))
+}
+
+pub fn pred_int(i: Core) -> Core {
+    Core::new(PredInt(i))
+}
+
+pub fn negate_int(i: Core) -> Core {
+    Core::new(NegateInt(i))
+}
+
+pub fn ind_int(target: Core, motive: Core, base: Core, up: Core, down: Core) -> Core {
+    Core::new(IndInt::new(target, motive, base, up, down))
+}
+
 pub fn atom() -> Core {
     Core::new(Atom)
 }
@@ -84,6 +125,14 @@ pub fn quote(s: impl Into<Symbol>) -> Core {
     Core::new(Quote(s.into()))
 }
 
+pub fn atom_eq(a: Core, b: Core) -> Core {
+    Core::new(AtomEq(a, b))
+}
+
+pub fn atom_apart(a: impl Into<Symbol>, b: impl Into<Symbol>) -> Core {
+    Core::new(AtomApart(a.into(), b.into()))
+}
+
 pub fn fun(ts: Vec<Core>) -> Core {
     Core::new(Fun(ts))
 }
@@ -100,6 +149,14 @@ pub fn pi_star(binders: Vec<(Symbol, Core)>, res_type: Core) -> Core {
     Core::new(PiStar { binders, res_type })
 }
 
+pub fn implicit_pi(x: impl Into<Symbol>, arg_type: Core, res_type: Core) -> Core {
+    Core::new(ImplicitPi {
+        arg_name: x.into(),
+        arg_type,
+        res_type,
+    })
+}
+
 pub fn lambda(x: impl Into<Symbol>, body: Core) -> Core {
     Core::new(Lambda {
         arg_name: x.into(),
@@ -111,10 +168,29 @@ pub fn lambda_star(params: Vec<Symbol>, body: Core) -> Core {
     Core::new(LambdaStar { params, body })
 }
 
+pub fn typed_lambda(x: impl Into<Symbol>, arg_type: Core, body: Core) -> Core {
+    Core::new(TypedLambda {
+        arg_name: x.into(),
+        arg_type,
+        body,
+    })
+}
+
+pub fn implicit_lambda(x: impl Into<Symbol>, body: Core) -> Core {
+    Core::new(ImplicitLambda {
+        arg_name: x.into(),
+        body,
+    })
+}
+
 pub fn app(fun: Core, arg: Core) -> Core {
     Core::new(App { fun, arg })
 }
 
+pub fn implicit_app(fun: Core, arg: Core) -> Core {
+    Core::new(ImplicitApp { fun, arg })
+}
+
 pub fn app_star(fun: Core, args: Vec<Core>) -> Core {
     Core::new(AppStar { fun, args })
 }
@@ -249,6 +325,18 @@ pub fn symm(e: Core) -> Core {
     Core::new(Symm(e))
 }
 
+pub fn trans(p1: Core, p2: Core) -> Core {
+    Core::new(Trans(p1, p2))
+}
+
+pub fn ind_eq(target: Core, motive: Core, base: Core) -> Core {
+    Core::new(IndEq {
+        target,
+        motive,
+        base,
+    })
+}
+
 pub fn either(l: Core, r: Core) -> Core {
     Core::new(Either(l, r))
 }
@@ -265,6 +353,42 @@ pub fn ind_either(t: Core, m: Core, l: Core, r: Core) -> Core {
     Core::new(IndEither::new(t, m, l, r))
 }
 
+pub fn enumeration(labels: Vec<Symbol>) -> Core {
+    Core::new(Enum(labels))
+}
+
+pub fn ind_enum(target: Core, motive: Core, cases: Vec<Core>) -> Core {
+    Core::new(IndEnum::new(target, motive, cases))
+}
+
+pub fn stream(t: Core) -> Core {
+    Core::new(Stream(t))
+}
+
+pub fn corec_stream(seed: Core, step: Core) -> Core {
+    Core::new(CorecStream::new(seed, step))
+}
+
+pub fn stream_head(s: Core) -> Core {
+    Core::new(StreamHead(s))
+}
+
+pub fn stream_tail(s: Core) -> Core {
+    Core::new(StreamTail(s))
+}
+
+pub fn make(record: Symbol, fields: Vec<(Symbol, Core)>) -> Core {
+    Core::new(Make::new(record, fields))
+}
+
+pub fn field(e: Core, x: Symbol) -> Core {
+    Core::new(Field(e, x))
+}
+
+pub fn projection(e: Core, x: Symbol, chain: Core) -> Core {
+    Core::new(Projection::new(e, x, chain))
+}
+
 pub fn trivial() -> Core {
     Core::new(Trivial)
 }
@@ -280,3 +404,27 @@ pub fn absurd() -> Core {
 pub fn ind_absurd(t: Core, m: Core) -> Core {
     Core::new(IndAbsurd::new(t, m))
 }
+
+pub fn case_split(target: impl Into<Symbol>, hole: impl Into<Symbol>) -> Core {
+    Core::new(CaseSplit::new(target.into(), hole.into()))
+}
+
+pub fn inferred_motive(eliminator: Eliminator, args: Vec<Core>) -> Core {
+    Core::new(InferredMotive { eliminator, args })
+}
+
+pub fn resolved(e: Core) -> Core {
+    Core::new(Resolved(e))
+}
+
+pub fn match_(target: Core, clauses: Vec<Clause>) -> Core {
+    Core::new(Match { target, clauses })
+}
+
+pub fn let_(bindings: Vec<(Symbol, Core)>, body: Core) -> Core {
+    Core::new(Let { bindings, body })
+}
+
+pub fn search(depth: usize) -> Core {
+    Core::new(Search::new(depth))
+}
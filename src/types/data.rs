@@ -0,0 +1,668 @@
+use crate::basics::{
+    is_var_name, Core, CoreInterface, Ctx, NeutralInterface, Renaming, The, Value, ValueInterface,
+    N, R,
+};
+use crate::errors::{Error, Result};
+use crate::fresh::freshen;
+use crate::normalize::read_back;
+use crate::symbol::Symbol;
+use crate::types::functions::{curried, do_ap};
+use crate::types::{cores, values};
+use std::any::Any;
+use std::collections::HashSet;
+
+/// A user-defined inductive family, as introduced by a top-level `(data ...)` form.
+///
+/// Parameters are fixed across the whole family, indices may vary between constructors.
+/// Each constructor takes the parameters followed by its own arguments, and its result type
+/// must be the family applied to exactly the parameters and some indices. The eliminator
+/// `ind-D` computes values but not types, since its motive is a family of types in `U`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DataDecl {
+    pub name: Symbol,
+    pub params: Vec<(Symbol, Core)>,
+    pub indices: Vec<(Symbol, Core)>,
+    pub constructors: Vec<Constructor>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Constructor {
+    pub name: Symbol,
+    pub args: Vec<(Symbol, Core)>,
+    pub result: Core,
+}
+
+/// A user-defined type former applied to its parameters and indices
+#[derive(Debug)]
+pub struct DataType {
+    decl: R<DataDecl>,
+    args: Vec<The>,
+}
+
+/// A constructor applied to the family's parameters and its own arguments
+#[derive(Debug)]
+pub struct DataCon {
+    decl: R<DataDecl>,
+    ctor: usize,
+    args: Vec<The>,
+}
+
+#[derive(Debug)]
+pub struct NeutralIndData {
+    decl: R<DataDecl>,
+    args: Vec<The>,
+    target: N,
+}
+
+impl DataDecl {
+    pub fn eliminator_name(&self) -> Symbol {
+        format!("ind-{}", self.name.name()).into()
+    }
+
+    /// If `t` is this family applied to its parameters, return the index expressions
+    fn indices_of(&self, t: &Core) -> Option<Vec<Core>> {
+        let family = cores::refer(self.name.clone());
+        let args = if t == &family {
+            vec![]
+        } else {
+            match t.try_as::<crate::types::functions::AppStar>() {
+                Some(app) if app.fun == family => app.args.clone(),
+                _ => return None,
+            }
+        };
+
+        if args.len() != self.params.len() + self.indices.len() {
+            return None;
+        }
+
+        let (params, indices) = args.split_at(self.params.len());
+        for ((p, _), arg) in self.params.iter().zip(params) {
+            if arg != &cores::refer(p.clone()) {
+                return None;
+            }
+        }
+        Some(indices.to_vec())
+    }
+
+    fn param_refs(&self) -> Vec<Core> {
+        binder_refs(&self.params)
+    }
+
+    fn family(&self, indices: Vec<Core>) -> Core {
+        apply(
+            cores::refer(self.name.clone()),
+            self.param_refs().into_iter().chain(indices).collect(),
+        )
+    }
+
+    fn all_names(&self) -> HashSet<Symbol> {
+        let mut names = HashSet::new();
+        names.insert(self.name.clone());
+        let mut add_binders = |binders: &[(Symbol, Core)]| {
+            for (x, t) in binders {
+                names.insert(x.clone());
+                names.extend(t.occurring_names());
+            }
+        };
+        add_binders(&self.params);
+        add_binders(&self.indices);
+        for c in &self.constructors {
+            add_binders(&c.args);
+        }
+        for c in &self.constructors {
+            names.insert(c.name.clone());
+            names.extend(c.result.occurring_names());
+        }
+        names
+    }
+}
+
+/// Check a data declaration and add its type former, constructors, and eliminator to the context
+pub fn declare(ctx: &Ctx, decl: DataDecl) -> Result<Ctx> {
+    let decl = R::new(decl);
+    let n_params = decl.params.len();
+
+    for name in std::iter::once(&decl.name).chain(decl.constructors.iter().map(|c| &c.name)) {
+        if !is_var_name(name) {
+            return Err(Error::NotAVarName(name.clone()));
+        }
+    }
+
+    let former_type = telescope(
+        decl.params.iter().chain(&decl.indices).cloned().collect(),
+        cores::universe(),
+    );
+    let mut ctx = {
+        let decl = decl.clone();
+        let arity = n_params + decl.indices.len();
+        ctx.define_primitive(decl.name.clone(), former_type, move |tv| {
+            curried(
+                tv,
+                arity,
+                R::new(move |args| {
+                    Value::new(DataType {
+                        decl: decl.clone(),
+                        args,
+                    })
+                }),
+            )
+        })?
+    };
+
+    for (i, c) in decl.constructors.iter().enumerate() {
+        if decl.indices_of(&c.result).is_none() {
+            return Err(Error::InvalidConstructorType(
+                c.name.clone(),
+                c.result.clone(),
+            ));
+        }
+        for (_, t) in &c.args {
+            if decl.indices_of(t).is_none() && t.occurring_names().contains(&decl.name) {
+                return Err(Error::InvalidRecursiveOccurrence(
+                    decl.name.clone(),
+                    t.clone(),
+                ));
+            }
+        }
+
+        // constructor arguments must be small enough to fit in the family's universe
+        with_telescope(&ctx, &Renaming::new(), &decl.params, |ctx, r| {
+            telescope(c.args.clone(), c.result.clone()).check(ctx, r, &values::universe())
+        })?;
+
+        let ctor_type = telescope(
+            decl.params.iter().chain(&c.args).cloned().collect(),
+            c.result.clone(),
+        );
+        let decl = decl.clone();
+        let arity = n_params + c.args.len();
+        ctx = ctx.define_primitive(c.name.clone(), ctor_type, move |tv| {
+            curried(
+                tv,
+                arity,
+                R::new(move |args| {
+                    Value::new(DataCon {
+                        decl: decl.clone(),
+                        ctor: i,
+                        args,
+                    })
+                }),
+            )
+        })?;
+    }
+
+    let elim_type = eliminator_type(&decl, &(&ctx.names_only() | &decl.all_names()));
+    let arity = n_params + 1 + decl.constructors.len() + decl.indices.len() + 1;
+    let elim_name = decl.eliminator_name();
+    ctx.define_primitive(elim_name, elim_type, move |tv| {
+        curried(tv, arity, R::new(move |args| do_ind_data(&decl, args)))
+    })
+}
+
+/// The type of `ind-D`:
+///   (Π (params... (mot (Π (indices...) (-> (D params... indices...) U))) methods...
+///       indices... (target (D params... indices...)))
+///     (mot indices... target))
+/// where each constructor's method takes the constructor's arguments followed by
+/// one inductive hypothesis per recursive argument.
+///
+/// The motive's values are in `U`, which is not in `U` itself, so the eliminator cannot
+/// define a type by cases on the target (there is no large elimination).
+fn eliminator_type(decl: &DataDecl, used: &HashSet<Symbol>) -> Core {
+    let mut used = used.clone();
+    let mut fresh = |x: String| {
+        let x = freshen(&used, &x.into());
+        used.insert(x.clone());
+        x
+    };
+
+    let mot = fresh("mot".to_string());
+    let target = fresh("target".to_string());
+    let index_refs = binder_refs(&decl.indices);
+
+    let motive_type = telescope(
+        decl.indices.clone(),
+        Core::fun(vec![decl.family(index_refs.clone())], cores::universe()),
+    );
+
+    let mut binders = decl.params.clone();
+    binders.push((mot.clone(), motive_type));
+
+    for c in &decl.constructors {
+        let mut method_binders = c.args.clone();
+        for (a, t) in &c.args {
+            if let Some(indices) = decl.indices_of(t) {
+                let ih = fresh(format!("ih-{}", a.name()));
+                let ih_type = apply(
+                    cores::refer(mot.clone()),
+                    indices
+                        .into_iter()
+                        .chain([cores::refer(a.clone())])
+                        .collect(),
+                );
+                method_binders.push((ih, ih_type));
+            }
+        }
+
+        let value = apply(
+            cores::refer(c.name.clone()),
+            decl.param_refs()
+                .into_iter()
+                .chain(binder_refs(&c.args))
+                .collect(),
+        );
+        let result = apply(
+            cores::refer(mot.clone()),
+            decl.indices_of(&c.result)
+                .unwrap()
+                .into_iter()
+                .chain([value])
+                .collect(),
+        );
+
+        let step = fresh(format!("step-{}", c.name.name()));
+        binders.push((step, telescope(method_binders, result)));
+    }
+
+    binders.extend(decl.indices.iter().cloned());
+    binders.push((target.clone(), decl.family(index_refs.clone())));
+
+    let result = apply(
+        cores::refer(mot),
+        index_refs
+            .into_iter()
+            .chain([cores::refer(target)])
+            .collect(),
+    );
+    telescope(binders, result)
+}
+
+fn do_ind_data(decl: &R<DataDecl>, args: Vec<The>) -> Value {
+    let n_params = decl.params.len();
+    let n_fixed = n_params + 1 + decl.constructors.len();
+    let (fixed, rest) = args.split_at(n_fixed);
+    let tgt = &rest.last().unwrap().1;
+
+    if let Some(con) = tgt.try_as::<DataCon>() {
+        let method = fixed[n_params + 1 + con.ctor].1.clone();
+        let ctor_args = &con.args[n_params..];
+
+        let mut result = ctor_args
+            .iter()
+            .fold(method, |f, The(_, x)| do_ap(&f, x.clone()));
+
+        for The(t, x) in ctor_args {
+            match t.try_as::<DataType>() {
+                Some(dt) if dt.decl.name == decl.name => {
+                    let mut ih_args = fixed.to_vec();
+                    ih_args.extend(dt.args[n_params..].iter().cloned());
+                    ih_args.push(The(t.clone(), x.clone()));
+                    result = do_ap(&result, do_ind_data(decl, ih_args));
+                }
+                _ => {}
+            }
+        }
+        return result;
+    }
+
+    if let Some((_, ne)) = tgt.as_neutral() {
+        let mot = &fixed[n_params].1;
+        let tv = rest
+            .iter()
+            .fold(mot.clone(), |f, The(_, x)| do_ap(&f, x.clone()));
+        return values::neutral(
+            tv,
+            NeutralIndData {
+                decl: decl.clone(),
+                args: args[..args.len() - 1].to_vec(),
+                target: ne.clone(),
+            },
+        );
+    }
+
+    unreachable!("{:?}", tgt)
+}
+
+impl ValueInterface for DataType {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn same(&self, other: &dyn ValueInterface) -> bool {
+        if let Some(other) = other.try_as::<Self>() {
+            self.decl.name == other.decl.name && same_args(&self.args, &other.args)
+        } else {
+            false
+        }
+    }
+
+    fn read_back_type(&self, ctx: &Ctx) -> Result<Core> {
+        read_back_app(ctx, cores::refer(self.decl.name.clone()), &self.args)
+    }
+
+    fn read_back(&self, ctx: &Ctx, tv: &Value, v: &Value) -> Result<Core> {
+        match v.try_as::<DataCon>() {
+            Some(con) if con.decl.name == self.decl.name => {
+                let name = con.decl.constructors[con.ctor].name.clone();
+                read_back_app(ctx, cores::refer(name), &con.args)
+            }
+            _ => Err(Error::TypeMismatchVar(v.clone(), tv.clone())),
+        }
+    }
+}
+
+impl ValueInterface for DataCon {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn same(&self, other: &dyn ValueInterface) -> bool {
+        if let Some(other) = other.try_as::<Self>() {
+            self.decl.name == other.decl.name
+                && self.ctor == other.ctor
+                && same_args(&self.args, &other.args)
+        } else {
+            false
+        }
+    }
+
+    fn read_back_type(&self, _ctx: &Ctx) -> Result<Core> {
+        Err(Error::NotATypeVar(Value::new(DataCon {
+            decl: self.decl.clone(),
+            ctor: self.ctor,
+            args: self.args.clone(),
+        })))
+    }
+}
+
+impl NeutralInterface for NeutralIndData {
+    fn read_back_neutral(&self, ctx: &Ctx) -> Result<Core> {
+        let elim = read_back_app(ctx, cores::refer(self.decl.eliminator_name()), &self.args)?;
+        Ok(cores::app(elim, self.target.read_back_neutral(ctx)?))
+    }
+}
+
+fn same_args(a: &[The], b: &[The]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).all(|(x, y)| x.1 == y.1)
+}
+
+fn read_back_app(ctx: &Ctx, f: Core, args: &[The]) -> Result<Core> {
+    args.iter()
+        .try_fold(f, |f, The(t, x)| Ok(cores::app(f, read_back(ctx, t, x)?)))
+}
+
+fn binder_refs(binders: &[(Symbol, Core)]) -> Vec<Core> {
+    binders
+        .iter()
+        .map(|(x, _)| cores::refer(x.clone()))
+        .collect()
+}
+
+fn telescope(binders: Vec<(Symbol, Core)>, body: Core) -> Core {
+    if binders.is_empty() {
+        body
+    } else {
+        Core::pi_star(binders, body)
+    }
+}
+
+fn apply(f: Core, args: Vec<Core>) -> Core {
+    if args.is_empty() {
+        f
+    } else {
+        Core::app_star(f, args)
+    }
+}
+
+/// Check the binder types in order, and run `k` with all of them bound as free variables
+fn with_telescope<T>(
+    ctx: &Ctx,
+    r: &Renaming,
+    binders: &[(Symbol, Core)],
+    k: impl FnOnce(&Ctx, &Renaming) -> Result<T>,
+) -> Result<T> {
+    match binders {
+        [] => k(ctx, r),
+        [(x, t), rest @ ..] => {
+            let t_out = t.is_type(ctx, r)?;
+            let x_hat = ctx.fresh(x);
+            let ctx_hat =
+                ctx.bind_free(x_hat.clone(), crate::normalize::val_in_ctx(ctx, &t_out))?;
+            with_telescope(&ctx_hat, &r.extend(x.clone(), x_hat), rest, k)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::alpha::is_alpha_equiv;
+    use crate::basics::{Core, Ctx};
+    use crate::errors::Error;
+    use crate::rep::{check_same, eval_normalize, norm};
+    use crate::types::cores;
+
+    fn ctx_with(decls: &[&str]) -> Ctx {
+        let mut ctx = Ctx::new();
+        for decl in decls {
+            eval_normalize(&mut ctx, decl).unwrap();
+        }
+        ctx
+    }
+
+    const TREE: &str = "(data Tree ((A U)) ()
+                           (leaf () (Tree A))
+                           (node ((l (Tree A)) (x A) (r (Tree A))) (Tree A)))";
+
+    const FIN: &str = "(data Fin () ((n Nat))
+                          (fzero ((n Nat)) (Fin (add1 n)))
+                          (fsucc ((n Nat) (i (Fin n))) (Fin (add1 n))))";
+
+    const EVEN: &str = "(data Even () ((n Nat))
+                           (even-zero () (Even zero))
+                           (even-plus-two ((n Nat) (e (Even n))) (Even (add1 (add1 n)))))";
+
+    fn parse(s: &str) -> Core {
+        s.parse().unwrap()
+    }
+
+    fn app(f: &str, args: Vec<Core>) -> Core {
+        args.into_iter().fold(Core::symbol(f), cores::app)
+    }
+
+    #[test]
+    fn type_formers_and_constructors_have_the_declared_types() {
+        let ctx = ctx_with(&[TREE, FIN]);
+        assert_eq!(
+            norm(&ctx, &parse("Tree")),
+            Ok(Core::the(
+                Core::pi("A", cores::universe(), cores::universe()),
+                Core::lambda("A", app("Tree", vec![Core::symbol("A")]))
+            ))
+        );
+        assert_eq!(
+            norm(&ctx, &parse("(Tree Atom)")),
+            Ok(Core::the(
+                cores::universe(),
+                app("Tree", vec![cores::atom()])
+            ))
+        );
+
+        let leaf = app("leaf", vec![cores::atom()]);
+        assert_eq!(
+            norm(&ctx, &parse("(node Atom (leaf Atom) 'x (leaf Atom))")),
+            Ok(Core::the(
+                app("Tree", vec![cores::atom()]),
+                app(
+                    "node",
+                    vec![cores::atom(), leaf.clone(), Core::quote("x"), leaf]
+                )
+            ))
+        );
+        assert_eq!(
+            norm(&ctx, &parse("(fsucc 1 (fzero 0))")),
+            Ok(Core::the(
+                app("Fin", vec![Core::nat(2)]),
+                app(
+                    "fsucc",
+                    vec![Core::nat(1), app("fzero", vec![Core::nat(0)])]
+                )
+            ))
+        );
+    }
+
+    #[test]
+    fn indices_are_checked() {
+        let ctx = ctx_with(&[FIN]);
+        assert!(check_same(
+            &ctx,
+            &parse("(Fin 3)"),
+            &parse("(fsucc 2 (fzero 1))"),
+            &parse("(fsucc 2 (fzero 1))")
+        )
+        .is_ok());
+        assert!(matches!(
+            check_same(
+                &ctx,
+                &parse("(Fin 3)"),
+                &parse("(fsucc 1 (fzero 0))"),
+                &parse("(fsucc 1 (fzero 0))")
+            ),
+            Err(Error::WrongType(_, _))
+        ));
+    }
+
+    #[test]
+    fn the_eliminator_computes_on_constructors() {
+        let mut ctx = ctx_with(&[TREE]);
+        eval_normalize(&mut ctx, "(claim size (Π ((A U)) (-> (Tree A) Nat)))").unwrap();
+        eval_normalize(
+            &mut ctx,
+            "(define size
+               (λ (A t)
+                 (ind-Tree A (λ (t) Nat)
+                   0
+                   (λ (l x r size-l size-r) (add1 (iter-Nat size-l size-r (λ (n) (add1 n)))))
+                   t)))",
+        )
+        .unwrap();
+
+        assert_eq!(
+            norm(
+                &ctx,
+                &parse(
+                    "(size Atom (node Atom (node Atom (leaf Atom) 'a (leaf Atom)) 'b (leaf Atom)))"
+                )
+            ),
+            Ok(Core::the(parse("Nat"), parse("2")))
+        );
+    }
+
+    #[test]
+    fn the_eliminator_is_dependent_over_indices() {
+        let mut ctx = ctx_with(&[EVEN]);
+        eval_normalize(
+            &mut ctx,
+            "(claim even-double (Π ((n Nat)) (Even (iter-Nat n 0 (λ (k) (add1 (add1 k)))))))",
+        )
+        .unwrap();
+        eval_normalize(
+            &mut ctx,
+            "(define even-double
+               (λ (n)
+                 (ind-Nat n
+                   (λ (n) (Even (iter-Nat n 0 (λ (k) (add1 (add1 k))))))
+                   even-zero
+                   (λ (n-1 e) (even-plus-two (iter-Nat n-1 0 (λ (k) (add1 (add1 k)))) e)))))",
+        )
+        .unwrap();
+
+        eval_normalize(&mut ctx, "(claim half (Π ((n Nat)) (-> (Even n) Nat)))").unwrap();
+        eval_normalize(
+            &mut ctx,
+            "(define half
+               (λ (n e)
+                 (ind-Even (λ (n e) Nat) 0 (λ (n e half-n) (add1 half-n)) n e)))",
+        )
+        .unwrap();
+
+        assert_eq!(
+            norm(&ctx, &parse("(half 6 (even-double 3))")),
+            Ok(Core::the(parse("Nat"), parse("3")))
+        );
+    }
+
+    #[test]
+    fn the_eliminator_is_neutral_on_neutral_targets() {
+        let ctx = ctx_with(&[TREE]);
+        let tree_atom = app("Tree", vec![cores::atom()]);
+        let step = ["l", "x", "r", "a", "b"]
+            .iter()
+            .rev()
+            .fold(Core::symbol("a"), |body, x| Core::lambda(*x, body));
+        assert!(is_alpha_equiv(
+            &norm(
+                &ctx,
+                &parse("(the (-> (Tree Atom) Nat) (λ (t) (ind-Tree Atom (λ (t) Nat) 0 (λ (l x r a b) a) t)))")
+            )
+            .unwrap(),
+            &Core::the(
+                Core::pi("t", tree_atom, cores::nat()),
+                Core::lambda(
+                    "t",
+                    app(
+                        "ind-Tree",
+                        vec![
+                            cores::atom(),
+                            Core::lambda("t", cores::nat()),
+                            Core::nat(0),
+                            step,
+                            Core::symbol("t")
+                        ]
+                    )
+                )
+            )
+        ));
+    }
+
+    #[test]
+    fn the_eliminator_cannot_compute_types() {
+        let mut ctx = ctx_with(&[TREE]);
+        eval_normalize(&mut ctx, "(claim leaf-type (-> (Tree Atom) U))").unwrap();
+        assert_eq!(
+            eval_normalize(
+                &mut ctx,
+                "(define leaf-type
+                   (λ (t) (ind-Tree Atom (λ (t) U) Atom (λ (l x r a b) Nat) t)))"
+            ),
+            Err(Error::WrongType(cores::universe_level(1), cores::universe()).to_string())
+        );
+    }
+
+    #[test]
+    fn constructors_must_return_the_declared_family() {
+        let mut ctx = ctx_with(&[]);
+        assert_eq!(
+            eval_normalize(&mut ctx, "(data Bad ((A U)) () (bad () Nat))"),
+            Err(Error::InvalidConstructorType("bad".into(), parse("Nat")).to_string())
+        );
+        assert_eq!(
+            eval_normalize(&mut ctx, "(data Bad ((A U)) () (bad () (Bad Nat)))"),
+            Err(Error::InvalidConstructorType("bad".into(), parse("(Bad Nat)")).to_string())
+        );
+    }
+
+    #[test]
+    fn recursive_occurrences_must_be_strictly_positive() {
+        let mut ctx = ctx_with(&[]);
+        assert_eq!(
+            eval_normalize(&mut ctx, "(data Bad () () (bad ((f (-> Bad Nat))) Bad))"),
+            Err(Error::InvalidRecursiveOccurrence("Bad".into(), parse("(-> Bad Nat)")).to_string())
+        );
+    }
+
+    #[test]
+    fn constructor_arguments_must_be_small() {
+        let mut ctx = ctx_with(&[]);
+        assert!(eval_normalize(&mut ctx, "(data Big () () (big ((A U)) Big))").is_err());
+    }
+}
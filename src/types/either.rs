@@ -3,12 +3,10 @@ use crate::basics::{
 };
 use crate::errors::{Error, Result};
 use crate::normalize::{read_back, val_in_ctx};
-use crate::symbol::Symbol;
 use crate::types::functions::do_ap;
 use crate::types::values::later;
-use crate::types::{cores, values};
+use crate::types::{cores, synth_type_level, values};
 use std::any::Any;
-use std::collections::HashSet;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Either<T>(pub T, pub T);
@@ -65,15 +63,25 @@ impl CoreInterface for Either<Core> {
     }
 
     fn synth(&self, ctx: &Ctx, r: &Renaming) -> Result<(Core, Core)> {
-        let u = values::universe();
-        let l_out = self.0.check(ctx, r, &u)?;
-        let r_out = self.1.check(ctx, r, &u)?;
-        Ok((cores::universe(), cores::either(l_out, r_out)))
+        let (l_out, l_level) = synth_type_level(ctx, r, &self.0)?;
+        let (r_out, r_level) = synth_type_level(ctx, r, &self.1)?;
+        Ok((
+            cores::universe_level(l_level.max(r_level)),
+            cores::either(l_out, r_out),
+        ))
     }
 }
 
 impl CoreInterface for Left<Core> {
-    impl_core_defaults!((0), as_any, same, occurring_names, no_synth, alpha_equiv);
+    impl_core_defaults!(
+        (0),
+        as_any,
+        same,
+        occurring_names,
+        no_synth,
+        alpha_equiv,
+        (resugar: left)
+    );
 
     fn val_of(&self, env: &Env) -> Value {
         values::left(later(env.clone(), self.0.clone()))
@@ -91,14 +99,18 @@ impl CoreInterface for Left<Core> {
             Err(Error::NotAnEitherType(tv.read_back_type(ctx).unwrap()))
         }
     }
-
-    fn resugar(&self) -> (HashSet<Symbol>, Core) {
-        unimplemented!()
-    }
 }
 
 impl CoreInterface for Right<Core> {
-    impl_core_defaults!((0), as_any, same, occurring_names, no_synth, alpha_equiv);
+    impl_core_defaults!(
+        (0),
+        as_any,
+        same,
+        occurring_names,
+        no_synth,
+        alpha_equiv,
+        (resugar: right)
+    );
 
     fn val_of(&self, env: &Env) -> Value {
         values::right(later(env.clone(), self.0.clone()))
@@ -116,10 +128,6 @@ impl CoreInterface for Right<Core> {
             Err(Error::NotAnEitherType(tv.read_back_type(ctx).unwrap()))
         }
     }
-
-    fn resugar(&self) -> (HashSet<Symbol>, Core) {
-        unimplemented!()
-    }
 }
 
 impl CoreInterface for IndEither {
@@ -0,0 +1,286 @@
+use crate::alpha;
+use crate::alpha::alpha_equiv_aux;
+use crate::basics::{
+    Core, CoreInterface, Ctx, Env, NeutralInterface, Renaming, The, Value, ValueInterface, N,
+};
+use crate::errors::{Error, Result};
+use crate::normalize::{read_back, val_in_ctx};
+use crate::symbol::Symbol;
+use crate::types::atom::Quote;
+use crate::types::functions::do_ap;
+use crate::types::values::later;
+use crate::types::{cores, values, MaybeTyped};
+use std::any::Any;
+use std::collections::HashSet;
+use std::fmt::Formatter;
+
+/// A finite type whose values are the listed atoms: `(Enum 'red 'green 'blue)`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Enum(pub Vec<Symbol>);
+
+/// `(ind-Enum target motive case ...)` with one case per label, in the order of the type.
+/// Once elaborated, the target is annotated with its `Enum` type, which tells the cases apart.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IndEnum {
+    target: MaybeTyped,
+    motive: Core,
+    cases: Vec<Core>,
+}
+
+#[derive(Debug)]
+pub struct NeutralIndEnum(pub N, pub Value, pub The, pub Vec<The>);
+
+impl IndEnum {
+    pub fn new(target: Core, motive: Core, cases: Vec<Core>) -> Self {
+        IndEnum {
+            target: MaybeTyped::Plain(target),
+            motive,
+            cases,
+        }
+    }
+
+    pub fn typed(enum_type: Core, target: Core, motive: Core, cases: Vec<Core>) -> Self {
+        IndEnum {
+            target: MaybeTyped::The(enum_type, target),
+            motive,
+            cases,
+        }
+    }
+}
+
+impl CoreInterface for Enum {
+    impl_core_defaults!((0), as_any, same, check_by_synth);
+
+    fn occurring_names(&self) -> HashSet<Symbol> {
+        HashSet::new()
+    }
+
+    fn val_of(&self, _env: &Env) -> Value {
+        Value::new(self.clone())
+    }
+
+    fn is_type(&self, _ctx: &Ctx, _r: &Renaming) -> Result<Core> {
+        for (i, label) in self.0.iter().enumerate() {
+            if self.0[..i].contains(label) {
+                return Err(Error::DuplicateLabel(label.clone()));
+            }
+        }
+        Ok(Core::new(self.clone()))
+    }
+
+    fn synth(&self, ctx: &Ctx, r: &Renaming) -> Result<(Core, Core)> {
+        Ok((cores::universe(), self.is_type(ctx, r)?))
+    }
+
+    fn alpha_equiv_aux(
+        &self,
+        other: &dyn CoreInterface,
+        _lvl: usize,
+        _b1: &alpha::Bindings,
+        _b2: &alpha::Bindings,
+    ) -> bool {
+        CoreInterface::same(self, other)
+    }
+
+    fn resugar(&self) -> (HashSet<Symbol>, Core) {
+        (HashSet::new(), Core::new(self.clone()))
+    }
+}
+
+impl CoreInterface for IndEnum {
+    impl_core_defaults!(
+        (target, motive, cases),
+        as_any,
+        same,
+        no_type,
+        check_by_synth
+    );
+
+    fn occurring_names(&self) -> HashSet<Symbol> {
+        let mut names = &self.target.occurring_names() | &self.motive.occurring_names();
+        for case in &self.cases {
+            names.extend(case.occurring_names());
+        }
+        names
+    }
+
+    fn val_of(&self, env: &Env) -> Value {
+        match &self.target {
+            MaybeTyped::Plain(_) => unimplemented!("evaluate a desugared ind-Enum instead"),
+            MaybeTyped::The(et, tgt) => do_ind_enum(
+                later(env.clone(), et.clone()),
+                later(env.clone(), tgt.clone()),
+                later(env.clone(), self.motive.clone()),
+                self.cases
+                    .iter()
+                    .map(|c| later(env.clone(), c.clone()))
+                    .collect(),
+            ),
+        }
+    }
+
+    fn synth(&self, ctx: &Ctx, r: &Renaming) -> Result<(Core, Core)> {
+        let (tgt_t, tgt_out) = match &self.target {
+            MaybeTyped::Plain(tgt) => tgt.synth(ctx, r)?,
+            MaybeTyped::The(et, tgt) => cores::the(et.clone(), tgt.clone()).synth(ctx, r)?,
+        };
+        let tgt_tv = val_in_ctx(ctx, &tgt_t);
+        let labels = match tgt_tv.try_as::<Enum>() {
+            Some(Enum(labels)) => labels,
+            None => return Err(Error::NotAnEnumType(tgt_t)),
+        };
+        if labels.len() != self.cases.len() {
+            return Err(Error::WrongArity(Core::new(self.clone())));
+        }
+
+        let mot_out = self.motive.check(
+            ctx,
+            r,
+            &pi_type!(((_x as "x", tgt_tv.clone())), values::universe()),
+        )?;
+        let mot_val = val_in_ctx(ctx, &mot_out);
+        let cases_out = labels
+            .iter()
+            .zip(&self.cases)
+            .map(|(label, case)| case.check(ctx, r, &do_ap(&mot_val, values::quote(label.clone()))))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok((
+            cores::app(mot_out.clone(), tgt_out.clone()),
+            Core::new(IndEnum::typed(tgt_t, tgt_out, mot_out, cases_out)),
+        ))
+    }
+
+    fn alpha_equiv_aux(
+        &self,
+        other: &dyn CoreInterface,
+        lvl: usize,
+        b1: &alpha::Bindings,
+        b2: &alpha::Bindings,
+    ) -> bool {
+        match other.try_as::<Self>() {
+            Some(other) if self.cases.len() == other.cases.len() => {
+                self.target.alpha_equiv_aux(&other.target, lvl, b1, b2)
+                    && alpha_equiv_aux(lvl, b1, b2, &self.motive, &other.motive)
+                    && self
+                        .cases
+                        .iter()
+                        .zip(&other.cases)
+                        .all(|(c1, c2)| alpha_equiv_aux(lvl, b1, b2, c1, c2))
+            }
+            _ => false,
+        }
+    }
+
+    fn resugar(&self) -> (HashSet<Symbol>, Core) {
+        let (mut names, target) = self.target.resugar();
+        let (mot_names, motive) = self.motive.resugar();
+        names.extend(mot_names);
+        let mut cases = vec![];
+        for case in &self.cases {
+            let (case_names, case) = case.resugar();
+            names.extend(case_names);
+            cases.push(case);
+        }
+        (
+            names,
+            Core::new(IndEnum {
+                target,
+                motive,
+                cases,
+            }),
+        )
+    }
+}
+
+impl std::fmt::Display for Enum {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "(Enum")?;
+        for label in &self.0 {
+            write!(f, " '{}", label.name())?;
+        }
+        write!(f, ")")
+    }
+}
+
+impl std::fmt::Display for IndEnum {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.target {
+            MaybeTyped::Plain(tgt) => write!(f, "(ind-Enum {} {}", tgt, self.motive)?,
+            MaybeTyped::The(et, tgt) => {
+                write!(f, "(ind-Enum (the {} {}) {}", et, tgt, self.motive)?
+            }
+        }
+        for case in &self.cases {
+            write!(f, " {}", case)?;
+        }
+        write!(f, ")")
+    }
+}
+
+impl ValueInterface for Enum {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn same(&self, other: &dyn ValueInterface) -> bool {
+        if let Some(other) = other.try_as::<Self>() {
+            self == other
+        } else {
+            false
+        }
+    }
+
+    fn read_back_type(&self, _ctx: &Ctx) -> Result<Core> {
+        Ok(Core::new(self.clone()))
+    }
+
+    fn read_back(&self, _ctx: &Ctx, tv: &Value, v: &Value) -> Result<Core> {
+        match v.try_as::<Quote>() {
+            Some(Quote(s)) if self.0.contains(s) => Ok(cores::quote(s.clone())),
+            _ => Err(Error::TypeMismatchVar(v.clone(), tv.clone())),
+        }
+    }
+}
+
+fn do_ind_enum(enum_tv: Value, tgt: Value, mot: Value, cases: Vec<Value>) -> Value {
+    let labels = match enum_tv.try_as::<Enum>() {
+        Some(Enum(labels)) => labels,
+        None => unreachable!("{:?}", enum_tv),
+    };
+
+    if let Some(Quote(s)) = tgt.try_as::<Quote>() {
+        let i = labels.iter().position(|l| l == s).unwrap();
+        return cases[i].clone();
+    }
+
+    if let Some((_, ne)) = tgt.as_neutral() {
+        let mot_tv = pi_type!(((_x as "x", enum_tv.clone())), values::universe());
+        let cases = labels
+            .iter()
+            .zip(cases)
+            .map(|(l, c)| The(do_ap(&mot, values::quote(l.clone())), c))
+            .collect();
+        return values::neutral(
+            do_ap(&mot, tgt.clone()),
+            NeutralIndEnum(ne.clone(), enum_tv.clone(), The(mot_tv, mot), cases),
+        );
+    }
+
+    unreachable!("{:?}", tgt)
+}
+
+impl NeutralInterface for NeutralIndEnum {
+    fn read_back_neutral(&self, ctx: &Ctx) -> Result<Core> {
+        let NeutralIndEnum(tgt, enum_tv, The(mot_t, mot), cases) = self;
+        Ok(Core::new(IndEnum::typed(
+            enum_tv.read_back_type(ctx)?,
+            tgt.read_back_neutral(ctx)?,
+            read_back(ctx, mot_t, mot)?,
+            cases
+                .iter()
+                .map(|The(t, c)| read_back(ctx, t, c))
+                .collect::<Result<_>>()?,
+        )))
+    }
+}
@@ -8,7 +8,7 @@ use crate::symbol::Symbol;
 use crate::typechecker::{convert, same_type};
 use crate::types::functions::{do_ap, Pi};
 use crate::types::values::later;
-use crate::types::{cores, values};
+use crate::types::{cores, synth_type_level, values};
 use std::any::Any;
 use std::collections::HashSet;
 
@@ -41,6 +41,28 @@ pub struct Cong2(pub Core, pub Core, pub Core);
 #[derive(Debug, Clone, PartialEq)]
 pub struct Symm(pub Core);
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct Trans(pub Core, pub Core);
+
+#[derive(Debug)]
+pub struct NeutralTrans1(N, The);
+
+#[derive(Debug)]
+pub struct NeutralTrans2(The, N);
+
+#[derive(Debug)]
+pub struct NeutralTrans12(N, N);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct IndEq {
+    pub target: Core,
+    pub motive: Core,
+    pub base: Core,
+}
+
+#[derive(Debug)]
+pub struct NeutralIndEq(N, The, The);
+
 impl CoreInterface for Equal<Core> {
     impl_core_defaults!(
         (typ, from, to),
@@ -69,11 +91,14 @@ impl CoreInterface for Equal<Core> {
     }
 
     fn synth(&self, ctx: &Ctx, r: &Renaming) -> Result<(Core, Core)> {
-        let a_out = self.typ.check(ctx, r, &values::universe())?;
+        let (a_out, level) = synth_type_level(ctx, r, &self.typ)?;
         let av = val_in_ctx(ctx, &a_out);
         let from_out = self.from.check(ctx, r, &av)?;
         let to_out = self.to.check(ctx, r, &av)?;
-        Ok((cores::universe(), cores::equal(a_out, from_out, to_out)))
+        Ok((
+            cores::universe_level(level),
+            cores::equal(a_out, from_out, to_out),
+        ))
     }
 }
 
@@ -92,8 +117,12 @@ impl CoreInterface for Same<Core> {
         values::same(later(env.clone(), self.0.clone()))
     }
 
-    fn synth(&self, _ctx: &Ctx, _r: &Renaming) -> Result<(Core, Core)> {
-        todo!()
+    fn synth(&self, ctx: &Ctx, r: &Renaming) -> Result<(Core, Core)> {
+        let (t_out, e_out) = self.0.synth(ctx, r)?;
+        Ok((
+            cores::equal(t_out, e_out.clone(), e_out.clone()),
+            cores::same(e_out),
+        ))
     }
 
     fn check(&self, ctx: &Ctx, r: &Renaming, tv: &Value) -> Result<Core> {
@@ -300,12 +329,116 @@ impl CoreInterface for Symm {
     }
 }
 
+impl CoreInterface for Trans {
+    impl_core_defaults!(
+        (0, 1),
+        as_any,
+        same,
+        occurring_names,
+        alpha_equiv,
+        no_type,
+        check_by_synth,
+        (resugar: trans)
+    );
+
+    fn val_of(&self, env: &Env) -> Value {
+        do_trans(
+            later(env.clone(), self.0.clone()),
+            later(env.clone(), self.1.clone()),
+        )
+    }
+
+    fn synth(&self, ctx: &Ctx, r: &Renaming) -> Result<(Core, Core)> {
+        let (p1_t_out, p1_out) = self.0.synth(ctx, r)?;
+        let (p2_t_out, p2_out) = self.1.synth(ctx, r)?;
+        let p1_t_outv = val_in_ctx(ctx, &p1_t_out);
+        let p2_t_outv = val_in_ctx(ctx, &p2_t_out);
+        if let Some(Equal {
+            typ: av,
+            from: from_v,
+            to: mid_v,
+        }) = p1_t_outv.try_as::<Equal<Value>>()
+        {
+            if let Some(Equal {
+                typ: bv,
+                from: mid2_v,
+                to: to_v,
+            }) = p2_t_outv.try_as::<Equal<Value>>()
+            {
+                same_type(ctx, av, bv)?;
+                convert(ctx, av, mid_v, mid2_v)?;
+                Ok((
+                    values::equal(av.clone(), from_v.clone(), to_v.clone()).read_back_type(ctx)?,
+                    cores::trans(p1_out, p2_out),
+                ))
+            } else {
+                Err(Error::NotAnEqualType(p2_t_outv.read_back_type(ctx)?))
+            }
+        } else {
+            Err(Error::NotAnEqualType(p1_t_outv.read_back_type(ctx)?))
+        }
+    }
+}
+
+impl CoreInterface for IndEq {
+    impl_core_defaults!(
+        (target, motive, base),
+        as_any,
+        same,
+        occurring_names,
+        alpha_equiv,
+        no_type,
+        check_by_synth,
+        (resugar: ind_eq)
+    );
+
+    fn val_of(&self, env: &Env) -> Value {
+        do_ind_eq(
+            later(env.clone(), self.target.clone()),
+            later(env.clone(), self.motive.clone()),
+            later(env.clone(), self.base.clone()),
+        )
+    }
+
+    fn synth(&self, ctx: &Ctx, r: &Renaming) -> Result<(Core, Core)> {
+        let (tgt_t_out, tgt_out) = self.target.synth(ctx, r)?;
+        let tgt_t_outv = val_in_ctx(ctx, &tgt_t_out);
+        if let Some(Equal {
+            typ: av,
+            from: from_v,
+            to: to_v,
+        }) = tgt_t_outv.try_as::<Equal<Value>>()
+        {
+            let mot_out =
+                self.motive
+                    .check(ctx, r, &ind_eq_motive_type(av.clone(), from_v.clone()))?;
+            let mot_v = val_in_ctx(ctx, &mot_out);
+            let b_out = self.base.check(
+                ctx,
+                r,
+                &do_ap(&do_ap(&mot_v, from_v.clone()), values::same(from_v.clone())),
+            )?;
+            Ok((
+                cores::app(
+                    cores::app(mot_out.clone(), read_back(ctx, av, to_v)?),
+                    tgt_out.clone(),
+                ),
+                cores::ind_eq(tgt_out, mot_out, b_out),
+            ))
+        } else {
+            Err(Error::NotAnEqualType(tgt_t_outv.read_back_type(ctx)?))
+        }
+    }
+}
+
 impl_sexpr_display!(T: Equal<T>, ("=", typ, from, to));
 impl_sexpr_display!(T: Same<T>, ("same", 0));
 impl_sexpr_display!(Replace, ("replace", target, motive, base));
 impl_sexpr_display!(Cong, ("cong", 0, 1));
 impl_sexpr_display!(Cong2, ("cong2", 0, 1, 2));
 impl_sexpr_display!(Symm, ("symm", 0));
+impl_sexpr_display!(Trans, ("trans", 0, 1));
+impl_sexpr_display!(IndEq, ("ind-=", target, motive, base));
 
 impl ValueInterface for Equal<Value> {
     fn as_any(&self) -> &dyn Any {
@@ -397,6 +530,155 @@ fn do_cong(tgt_v: Value, _b_v: Value, fun_v: Value) -> Value {
     todo!()
 }
 
+fn do_trans(tgt1_v: Value, tgt2_v: Value) -> Value {
+    match (
+        tgt1_v.try_as::<Same<Value>>(),
+        tgt2_v.try_as::<Same<Value>>(),
+    ) {
+        (Some(Same(v)), Some(_)) => return values::same(v.clone()),
+        (Some(Same(from_v)), None) => {
+            if let Some((eql, ne2)) = tgt2_v.as_neutral() {
+                if let Some(Equal {
+                    typ: a_v, to: to_v, ..
+                }) = eql.try_as::<Equal<Value>>()
+                {
+                    return values::neutral(
+                        values::equal(a_v.clone(), from_v.clone(), to_v.clone()),
+                        NeutralTrans2(
+                            The(
+                                values::equal(a_v.clone(), from_v.clone(), from_v.clone()),
+                                values::same(from_v.clone()),
+                            ),
+                            ne2.clone(),
+                        ),
+                    );
+                }
+            }
+        }
+        (None, Some(Same(to_v))) => {
+            if let Some((eql, ne1)) = tgt1_v.as_neutral() {
+                if let Some(Equal {
+                    typ: a_v,
+                    from: from_v,
+                    ..
+                }) = eql.try_as::<Equal<Value>>()
+                {
+                    return values::neutral(
+                        values::equal(a_v.clone(), from_v.clone(), to_v.clone()),
+                        NeutralTrans1(
+                            ne1.clone(),
+                            The(
+                                values::equal(a_v.clone(), to_v.clone(), to_v.clone()),
+                                values::same(to_v.clone()),
+                            ),
+                        ),
+                    );
+                }
+            }
+        }
+        (None, None) => {
+            if let (Some((eql1, ne1)), Some((eql2, ne2))) =
+                (tgt1_v.as_neutral(), tgt2_v.as_neutral())
+            {
+                if let (
+                    Some(Equal {
+                        typ: a_v,
+                        from: from_v,
+                        ..
+                    }),
+                    Some(Equal { to: to_v, .. }),
+                ) = (eql1.try_as::<Equal<Value>>(), eql2.try_as::<Equal<Value>>())
+                {
+                    return values::neutral(
+                        values::equal(a_v.clone(), from_v.clone(), to_v.clone()),
+                        NeutralTrans12(ne1.clone(), ne2.clone()),
+                    );
+                }
+            }
+        }
+    }
+
+    unreachable!()
+}
+
+fn ind_eq_motive_type(a_v: Value, from_v: Value) -> Value {
+    pi_type!(
+        ((to, a_v.clone())),
+        pi_type!(
+            ((_p as "p", values::equal(a_v.clone(), from_v.clone(), to))),
+            values::universe()
+        )
+    )
+}
+
+fn do_ind_eq(tgt_v: Value, mot_v: Value, b_v: Value) -> Value {
+    if let Some(Same(_)) = tgt_v.try_as::<Same<Value>>() {
+        return b_v;
+    }
+
+    if let Some((eql, ne)) = tgt_v.as_neutral() {
+        if let Some(Equal {
+            typ: a_v,
+            from: from_v,
+            to: to_v,
+        }) = eql.try_as::<Equal<Value>>()
+        {
+            let b_tv = do_ap(&do_ap(&mot_v, from_v.clone()), values::same(from_v.clone()));
+            return values::neutral(
+                do_ap(&do_ap(&mot_v, to_v.clone()), tgt_v.clone()),
+                NeutralIndEq(
+                    ne.clone(),
+                    The(ind_eq_motive_type(a_v.clone(), from_v.clone()), mot_v),
+                    The(b_tv, b_v),
+                ),
+            );
+        }
+    }
+
+    unreachable!()
+}
+
+impl NeutralInterface for NeutralTrans1 {
+    fn read_back_neutral(&self, ctx: &Ctx) -> Result<Core> {
+        let NeutralTrans1(tgt1, The(tgt2_tv, tgt2_v)) = self;
+        Ok(cores::trans(
+            tgt1.read_back_neutral(ctx)?,
+            read_back(ctx, tgt2_tv, tgt2_v)?,
+        ))
+    }
+}
+
+impl NeutralInterface for NeutralTrans2 {
+    fn read_back_neutral(&self, ctx: &Ctx) -> Result<Core> {
+        let NeutralTrans2(The(tgt1_tv, tgt1_v), tgt2) = self;
+        Ok(cores::trans(
+            read_back(ctx, tgt1_tv, tgt1_v)?,
+            tgt2.read_back_neutral(ctx)?,
+        ))
+    }
+}
+
+impl NeutralInterface for NeutralTrans12 {
+    fn read_back_neutral(&self, ctx: &Ctx) -> Result<Core> {
+        let NeutralTrans12(tgt1, tgt2) = self;
+        Ok(cores::trans(
+            tgt1.read_back_neutral(ctx)?,
+            tgt2.read_back_neutral(ctx)?,
+        ))
+    }
+}
+
+impl NeutralInterface for NeutralIndEq {
+    fn read_back_neutral(&self, ctx: &Ctx) -> Result<Core> {
+        let NeutralIndEq(tgt, The(mot_tv, mot_v), The(b_tv, b_v)) = self;
+        Ok(cores::ind_eq(
+            tgt.read_back_neutral(ctx)?,
+            read_back(ctx, mot_tv, mot_v)?,
+            read_back(ctx, b_tv, b_v)?,
+        ))
+    }
+}
+
 impl NeutralInterface for NeutralReplace {
     fn read_back_neutral(&self, ctx: &Ctx) -> Result<Core> {
         let NeutralReplace(tgt, The(mot_tv, mot_v), The(b_tv, b_v)) = self;
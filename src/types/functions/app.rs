@@ -1,10 +1,15 @@
-use crate::basics::{Core, CoreInterface, Ctx, Env, NeutralInterface, Renaming, The, Value, N};
+use crate::basics::{
+    Closure, Core, CoreInterface, Ctx, Env, NeutralInterface, Renaming, The, Value, N,
+};
 use crate::errors;
 use crate::errors::Error;
 use crate::normalize::{read_back, val_in_ctx};
 use crate::symbol::Symbol;
+use crate::typechecker::same_type;
+use crate::types::functions::Pi;
 use crate::types::values::later;
-use crate::types::{cores, functions, values};
+use crate::types::{cores, functions, synth_type_level};
+use crate::unify::Metas;
 use std::collections::HashSet;
 use std::fmt::{Display, Formatter};
 
@@ -44,19 +49,20 @@ impl CoreInterface for App {
     }
 
     fn is_type(&self, ctx: &Ctx, r: &Renaming) -> errors::Result<Core> {
-        match self.check(ctx, r, &values::universe()) {
-            Ok(t_out) => Ok(t_out),
+        match synth_type_level(ctx, r, self) {
+            Ok((t_out, _)) => Ok(t_out),
             Err(_) => Err(Error::NotAType(Core::new(self.clone()))),
         }
     }
 
-    fn synth(&self, _ctx: &Ctx, _r: &Renaming) -> errors::Result<(Core, Core)> {
-        panic!("use AppStar for synthesis")
+    fn synth(&self, ctx: &Ctx, r: &Renaming) -> errors::Result<(Core, Core)> {
+        // elaborated applications are re-checked when they are read back into types
+        cores::app_star(self.fun.clone(), vec![self.arg.clone()]).synth(ctx, r)
     }
 }
 
 impl CoreInterface for AppStar {
-    impl_core_defaults!((fun, arg), as_any, same, no_alpha_equiv, check_by_synth);
+    impl_core_defaults!((fun, arg), as_any, same, no_alpha_equiv);
 
     fn occurring_names(&self) -> HashSet<Symbol> {
         let mut names = self.fun.occurring_names();
@@ -71,27 +77,38 @@ impl CoreInterface for AppStar {
     }
 
     fn is_type(&self, ctx: &Ctx, r: &Renaming) -> errors::Result<Core> {
-        match self.check(ctx, r, &values::universe()) {
-            Ok(t_out) => Ok(t_out),
+        match synth_type_level(ctx, r, self) {
+            Ok((t_out, _)) => Ok(t_out),
             //Err(_) => Err(Error::NotAType(Core::new(self.clone()))),
             Err(e) => Err(e),
         }
     }
 
     fn synth(&self, ctx: &Ctx, r: &Renaming) -> errors::Result<(Core, Core)> {
-        let (rator_t, rator_out) = self.fun.synth(ctx, r)?;
-        match &self.args[..] {
-            [] => Err(Error::WrongArity(Core::app_star(rator_out, vec![]))),
-            [rand] => val_in_ctx(ctx, &rator_t).apply(ctx, r, &rator_out, rand),
-            [rands @ .., rand] => {
-                let (app0_t, app0) = AppStar {
-                    fun: self.fun.clone(),
-                    args: rands.to_vec(),
-                }
-                .synth(ctx, r)?;
-                val_in_ctx(ctx, &app0_t).apply(ctx, r, &app0, rand)
-            }
+        let mut metas = Metas::new();
+        let (tv, e_out) = self.elaborate(ctx, r, &mut metas)?;
+        if self.args.is_empty() {
+            return Err(Error::WrongArity(Core::app_star(e_out, vec![])));
+        }
+        metas.check_solved(&Core::new(self.clone()))?;
+        Ok((tv.read_back_type(ctx)?, e_out))
+    }
+
+    fn check(&self, ctx: &Ctx, r: &Renaming, tv: &Value) -> errors::Result<Core> {
+        let mut metas = Metas::new();
+        let (t, e_out) = self.elaborate(ctx, r, &mut metas)?;
+        // implicit arguments after the last explicit one can only be found from the expected type
+        let (t, e_out) = metas.instantiate(ctx, t, e_out);
+        if self.args.is_empty() && metas.is_empty() {
+            return Err(Error::WrongArity(Core::app_star(e_out, vec![])));
         }
+        same_type(ctx, &t, tv)?;
+        if self.args.is_empty() {
+            metas.check_solved(&self.fun)?;
+        } else {
+            metas.check_solved(&Core::new(self.clone()))?;
+        }
+        Ok(e_out)
     }
 
     fn resugar(&self) -> (HashSet<Symbol>, Core) {
@@ -99,6 +116,48 @@ impl CoreInterface for AppStar {
     }
 }
 
+impl AppStar {
+    /// Elaborate the application, passing a new metavariable for each implicit argument in front
+    /// of an explicit one. While there are unsolved metavariables, arguments are synthesized if
+    /// possible, so that their types can be unified with the types the function expects.
+    fn elaborate(
+        &self,
+        ctx: &Ctx,
+        r: &Renaming,
+        metas: &mut Metas,
+    ) -> errors::Result<(Value, Core)> {
+        let (rator_t, rator_out) = self.fun.synth(ctx, r)?;
+        let mut tv = val_in_ctx(ctx, &rator_t);
+        let mut e_out = rator_out;
+
+        for rand in &self.args {
+            (tv, e_out) = metas.instantiate(ctx, tv, e_out);
+            let (arg_tv, res_type) = match tv.try_as::<Pi<Value, Closure>>() {
+                Some(pi) => (metas.zonk(ctx, &pi.arg_type)?, pi.res_type.clone()),
+                None => return Err(Error::NotAFunctionType(tv.read_back_type(ctx)?)),
+            };
+
+            let rand_out = if metas.has_unsolved() {
+                match rand.synth(ctx, r) {
+                    Ok((rand_t, rand_out)) => {
+                        same_type(ctx, &val_in_ctx(ctx, &rand_t), &arg_tv)?;
+                        rand_out
+                    }
+                    Err(Error::CantDetermineType(_)) => rand.check(ctx, r, &arg_tv)?,
+                    Err(e) => return Err(e),
+                }
+            } else {
+                rand.check(ctx, r, &arg_tv)?
+            };
+
+            tv = res_type.val_of(val_in_ctx(ctx, &rand_out));
+            e_out = Core::app(e_out, rand_out);
+        }
+
+        Ok((tv, e_out))
+    }
+}
+
 impl Display for App {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "({} {})", self.fun, self.arg)
@@ -2,7 +2,7 @@ use crate::basics::{Core, CoreInterface, Ctx, Env, Renaming, Value};
 use crate::errors::Result;
 use crate::normalize::val_in_ctx;
 use crate::symbol::Symbol;
-use crate::types::{cores, values};
+use crate::types::{cores, synth_type_level};
 use std::collections::HashSet;
 use std::fmt::Formatter;
 
@@ -51,26 +51,29 @@ impl CoreInterface for Fun {
         match &self.0[..] {
             [a, b] => {
                 let z = ctx.fresh_binder(b, &Symbol::new("x"));
-                let a_out = a.check(ctx, r, &values::universe())?;
-                let b_out = b.check(
-                    &ctx.bind_free(z.clone(), val_in_ctx(ctx, &a_out))?,
-                    r,
-                    &values::universe(),
-                )?;
-                Ok((cores::universe(), Core::pi(z, a_out, b_out)))
+                let (a_out, a_level) = synth_type_level(ctx, r, a)?;
+                let (b_out, b_level) =
+                    synth_type_level(&ctx.bind_free(z.clone(), val_in_ctx(ctx, &a_out))?, r, b)?;
+                Ok((
+                    cores::universe_level(a_level.max(b_level)),
+                    Core::pi(z, a_out, b_out),
+                ))
             }
             [a, b, cs @ ..] => {
                 let z =
                     ctx.fresh_binder(&Core::app_star(b.clone(), cs.to_vec()), &Symbol::new("x"));
-                let a_out = a.check(ctx, r, &values::universe())?;
+                let (a_out, a_level) = synth_type_level(ctx, r, a)?;
                 let mut out_args = vec![b.clone()];
                 out_args.extend(cs.iter().cloned());
-                let t_out = cores::fun(out_args).check(
+                let (t_out, t_level) = synth_type_level(
                     &ctx.bind_free(z.clone(), val_in_ctx(ctx, &a_out))?,
                     r,
-                    &values::universe(),
+                    &cores::fun(out_args),
                 )?;
-                Ok((cores::universe(), Core::pi(z, a_out, t_out)))
+                Ok((
+                    cores::universe_level(a_level.max(t_level)),
+                    Core::pi(z, a_out, t_out),
+                ))
             }
             _ => todo!(),
         }
@@ -0,0 +1,338 @@
+use crate::alpha::alpha_equiv_aux;
+use crate::basics::{
+    Closure, Core, CoreInterface, Ctx, Env, NeutralInterface, Renaming, The, Value, ValueInterface,
+    N,
+};
+use crate::errors::Error;
+use crate::normalize::{read_back, val_in_ctx};
+use crate::symbol::Symbol;
+use crate::types::functions::lambda::Lambda;
+use crate::types::reference::NeutralVar;
+use crate::types::values::later;
+use crate::types::{
+    check_with_fresh_binding, cores, functions, is_type_with_fresh_binding, occurring_binder_names,
+    synth_type_level, values,
+};
+use crate::{alpha, errors};
+use std::any::Any;
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+
+/// A Π type whose argument is left out of applications: `(∀ ((E U)) (-> (List E) Nat))`.
+/// The argument is filled in by unification against the types of the explicit arguments
+/// and the type the application is checked against.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImplicitPi<T, C> {
+    pub arg_name: Symbol,
+    pub arg_type: T,
+    pub res_type: C,
+}
+
+/// A function of an implicit argument; inserted when checking against an implicit Π type
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImplicitLambda {
+    pub arg_name: Symbol,
+    pub body: Core,
+}
+
+/// Passing an implicit argument explicitly: `(@ f E)`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImplicitApp {
+    pub fun: Core,
+    pub arg: Core,
+}
+
+#[derive(Debug)]
+pub struct NeutralImplicitApp(pub N, pub The);
+
+impl CoreInterface for ImplicitPi<Core, Core> {
+    impl_core_defaults!((arg_name, arg_type, res_type), as_any, same, check_by_synth);
+
+    fn occurring_names(&self) -> HashSet<Symbol> {
+        &occurring_binder_names(&self.arg_name, &self.arg_type) | &self.res_type.occurring_names()
+    }
+
+    fn val_of(&self, env: &Env) -> Value {
+        values::implicit_pi(
+            self.arg_name.clone(),
+            later(env.clone(), self.arg_type.clone()),
+            Closure::FirstOrder {
+                env: env.clone(),
+                var: self.arg_name.clone(),
+                expr: self.res_type.clone(),
+            },
+        )
+    }
+
+    fn is_type(&self, ctx: &Ctx, r: &Renaming) -> errors::Result<Core> {
+        let (y, a_out, b_out) =
+            is_type_with_fresh_binding(ctx, r, &self.arg_name, &self.arg_type, &self.res_type)?;
+        Ok(cores::implicit_pi(y, a_out, b_out))
+    }
+
+    fn synth(&self, ctx: &Ctx, r: &Renaming) -> errors::Result<(Core, Core)> {
+        let (x_hat, a_out, b_out, level) =
+            check_with_fresh_binding(ctx, r, &self.arg_name, &self.arg_type, &self.res_type)?;
+        Ok((
+            cores::universe_level(level),
+            cores::implicit_pi(x_hat, a_out, b_out),
+        ))
+    }
+
+    fn alpha_equiv_aux(
+        &self,
+        other: &dyn CoreInterface,
+        lvl: usize,
+        b1: &alpha::Bindings,
+        b2: &alpha::Bindings,
+    ) -> bool {
+        if let Some(other) = other.as_any().downcast_ref::<Self>() {
+            alpha_equiv_aux(lvl, b1, b2, &self.arg_type, &other.arg_type)
+                && alpha_equiv_aux(
+                    1 + lvl,
+                    &b1.bind(&self.arg_name, lvl),
+                    &b2.bind(&other.arg_name, lvl),
+                    &self.res_type,
+                    &other.res_type,
+                )
+        } else {
+            false
+        }
+    }
+
+    fn resugar(&self) -> (HashSet<Symbol>, Core) {
+        let (arg_names, arg_type) = self.arg_type.resugar();
+        let (mut res_names, res_type) = self.res_type.resugar();
+        res_names.remove(&self.arg_name);
+        (
+            &arg_names | &res_names,
+            cores::implicit_pi(self.arg_name.clone(), arg_type, res_type),
+        )
+    }
+}
+
+impl CoreInterface for ImplicitLambda {
+    impl_core_defaults!((arg_name, body), as_any, same, no_type, no_synth);
+
+    fn occurring_names(&self) -> HashSet<Symbol> {
+        let mut names = self.body.occurring_names();
+        names.insert(self.arg_name.clone());
+        names
+    }
+
+    fn val_of(&self, env: &Env) -> Value {
+        values::lambda(
+            self.arg_name.clone(),
+            Closure::FirstOrder {
+                env: env.clone(),
+                var: self.arg_name.clone(),
+                expr: self.body.clone(),
+            },
+        )
+    }
+
+    fn check(&self, ctx: &Ctx, r: &Renaming, tv: &Value) -> errors::Result<Core> {
+        match tv.try_as::<ImplicitPi<Value, Closure>>() {
+            Some(pi) => {
+                let (x_hat, b_out) =
+                    check_under_implicit_binder(ctx, r, &self.arg_name, pi, |ctx, r, tv| {
+                        self.body.check(ctx, r, tv)
+                    })?;
+                Ok(cores::implicit_lambda(x_hat, b_out))
+            }
+            None => Err(Error::NotAFunctionType(tv.read_back_type(ctx)?)),
+        }
+    }
+
+    fn alpha_equiv_aux(
+        &self,
+        other: &dyn CoreInterface,
+        lvl: usize,
+        b1: &alpha::Bindings,
+        b2: &alpha::Bindings,
+    ) -> bool {
+        if let Some(other) = other.as_any().downcast_ref::<Self>() {
+            alpha_equiv_aux(
+                1 + lvl,
+                &b1.bind(&self.arg_name, lvl),
+                &b2.bind(&other.arg_name, lvl),
+                &self.body,
+                &other.body,
+            )
+        } else {
+            false
+        }
+    }
+
+    fn resugar(&self) -> (HashSet<Symbol>, Core) {
+        let (mut names, body) = self.body.resugar();
+        names.remove(&self.arg_name);
+        (names, cores::implicit_lambda(self.arg_name.clone(), body))
+    }
+}
+
+impl CoreInterface for ImplicitApp {
+    impl_core_defaults!(
+        (fun, arg),
+        as_any,
+        same,
+        occurring_names,
+        alpha_equiv,
+        check_by_synth
+    );
+
+    fn val_of(&self, env: &Env) -> Value {
+        functions::do_ap(
+            &later(env.clone(), self.fun.clone()),
+            later(env.clone(), self.arg.clone()),
+        )
+    }
+
+    fn is_type(&self, ctx: &Ctx, r: &Renaming) -> errors::Result<Core> {
+        match synth_type_level(ctx, r, self) {
+            Ok((t_out, _)) => Ok(t_out),
+            Err(_) => Err(Error::NotAType(Core::new(self.clone()))),
+        }
+    }
+
+    fn synth(&self, ctx: &Ctx, r: &Renaming) -> errors::Result<(Core, Core)> {
+        let (fun_t, fun_out) = self.fun.synth(ctx, r)?;
+        let fun_tv = val_in_ctx(ctx, &fun_t);
+        match fun_tv.try_as::<ImplicitPi<Value, Closure>>() {
+            Some(pi) => {
+                let arg_out = self.arg.check(ctx, r, &pi.arg_type)?;
+                Ok((
+                    pi.res_type
+                        .val_of(val_in_ctx(ctx, &arg_out))
+                        .read_back_type(ctx)?,
+                    cores::implicit_app(fun_out, arg_out),
+                ))
+            }
+            None => Err(Error::NotAnImplicitFunctionType(fun_t)),
+        }
+    }
+
+    fn resugar(&self) -> (HashSet<Symbol>, Core) {
+        let (fun_names, fun) = self.fun.resugar();
+        let (arg_names, arg) = self.arg.resugar();
+        (&fun_names | &arg_names, cores::implicit_app(fun, arg))
+    }
+}
+
+/// Anything checked against an implicit Π type is abstracted over the implicit argument
+pub fn check_with_implicit_lambda(
+    ctx: &Ctx,
+    r: &Renaming,
+    e: &Core,
+    tv: &Value,
+) -> errors::Result<Core> {
+    let pi = tv.try_as::<ImplicitPi<Value, Closure>>().unwrap();
+    let (x_hat, body) =
+        check_under_implicit_binder(ctx, r, &pi.arg_name, pi, |ctx, r, tv| e.check(ctx, r, tv))?;
+    Ok(cores::implicit_lambda(x_hat, body))
+}
+
+/// Check something in the scope of an implicit Π's argument. Like the binders of ordinary
+/// functions, the argument may be referred to by the name it has in the Π type.
+fn check_under_implicit_binder(
+    ctx: &Ctx,
+    r: &Renaming,
+    x: &Symbol,
+    pi: &ImplicitPi<Value, Closure>,
+    check: impl FnOnce(&Ctx, &Renaming, &Value) -> errors::Result<Core>,
+) -> errors::Result<(Symbol, Core)> {
+    let x_hat = ctx.fresh(x);
+    let b_out = check(
+        &ctx.bind_free(x_hat.clone(), pi.arg_type.clone())?,
+        &r.extend(x.clone(), x_hat.clone()),
+        &pi.res_type.val_of(values::neutral(
+            pi.arg_type.clone(),
+            NeutralVar(x_hat.clone()),
+        )),
+    )?;
+    Ok((x_hat, b_out))
+}
+
+impl Display for ImplicitPi<Core, Core> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "(∀ (({} {})) {})",
+            self.arg_name.name(),
+            self.arg_type,
+            self.res_type
+        )
+    }
+}
+
+impl Display for ImplicitLambda {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "(λ-implicit ({}) {})", self.arg_name.name(), self.body)
+    }
+}
+
+impl Display for ImplicitApp {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "(@ {} {})", self.fun, self.arg)
+    }
+}
+
+impl ValueInterface for ImplicitPi<Value, Closure> {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn same(&self, other: &dyn ValueInterface) -> bool {
+        // the result types are closures, so compare the types they read back as
+        let ctx = Ctx::new();
+        match (other.try_as::<Self>(), self.read_back_type(&ctx)) {
+            (Some(other), Ok(t1)) => match other.read_back_type(&ctx) {
+                Ok(t2) => alpha::is_alpha_equiv(&t1, &t2),
+                Err(_) => false,
+            },
+            _ => false,
+        }
+    }
+
+    fn read_back_type(&self, ctx: &Ctx) -> errors::Result<Core> {
+        let ae = self.arg_type.read_back_type(ctx)?;
+        let x_hat = ctx.fresh(&self.arg_name);
+
+        let ctx_hat = ctx.bind_free(x_hat.clone(), self.arg_type.clone())?;
+        let r = self
+            .res_type
+            .val_of(values::neutral(
+                self.arg_type.clone(),
+                NeutralVar(x_hat.clone()),
+            ))
+            .read_back_type(&ctx_hat)?;
+        Ok(cores::implicit_pi(x_hat, ae, r))
+    }
+
+    fn read_back(&self, ctx: &Ctx, _tv: &Value, f: &Value) -> errors::Result<Core> {
+        let x = match f.try_as::<Lambda<Closure>>() {
+            Some(lam) => &lam.arg_name,
+            None => &self.arg_name,
+        };
+        let x_hat = ctx.fresh(x);
+        let x_val = values::neutral(self.arg_type.clone(), NeutralVar(x_hat.clone()));
+
+        let body = read_back(
+            &ctx.bind_free(x_hat.clone(), self.arg_type.clone())?,
+            &self.res_type.val_of(x_val.clone()),
+            &functions::do_ap(f, x_val),
+        )?;
+
+        Ok(cores::implicit_lambda(x_hat, body))
+    }
+}
+
+impl NeutralInterface for NeutralImplicitApp {
+    fn read_back_neutral(&self, ctx: &Ctx) -> errors::Result<Core> {
+        let The(typ, val) = &self.1;
+        Ok(cores::implicit_app(
+            self.0.read_back_neutral(ctx)?,
+            read_back(ctx, typ, val)?,
+        ))
+    }
+}
@@ -1,11 +1,13 @@
 use crate::alpha::alpha_equiv_aux;
 use crate::basics::{Closure, Core, CoreInterface, Ctx, Env, Renaming, Value, ValueInterface};
 use crate::errors::Error;
+use crate::normalize::val_in_ctx;
 use crate::symbol::Symbol;
+use crate::typechecker::same_type;
 use crate::types::functions::Pi;
 use crate::types::reference::NeutralVar;
 use crate::types::values::lambda;
-use crate::types::{cores, values};
+use crate::types::{cores, occurring_binder_names, values};
 use crate::{alpha, errors, resugar};
 use std::any::Any;
 use std::collections::HashSet;
@@ -25,6 +27,14 @@ pub struct LambdaStar {
     pub body: Core,
 }
 
+/// A function whose argument type is given, `(λ ((x A)) body)`, so that it can synthesize a Π type
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypedLambda {
+    pub arg_name: Symbol,
+    pub arg_type: Core,
+    pub body: Core,
+}
+
 impl<B> Lambda<B> {
     pub fn new(arg_name: Symbol, body: B) -> Self {
         Lambda { arg_name, body }
@@ -129,6 +139,72 @@ impl CoreInterface for LambdaStar {
     }
 }
 
+impl CoreInterface for TypedLambda {
+    impl_core_defaults!((arg_name, arg_type, body), as_any, same, no_type);
+
+    fn occurring_names(&self) -> HashSet<Symbol> {
+        &occurring_binder_names(&self.arg_name, &self.arg_type) | &self.body.occurring_names()
+    }
+
+    fn val_of(&self, _env: &Env) -> Value {
+        panic!("Attempt to evaluate typed lambda (should have been elaborated to `Lambda`)")
+    }
+
+    fn synth(&self, ctx: &Ctx, r: &Renaming) -> errors::Result<(Core, Core)> {
+        let a_out = self.arg_type.is_type(ctx, r)?;
+        let x_hat = ctx.fresh(&self.arg_name);
+        let (b_t_out, b_out) = self.body.synth(
+            &ctx.bind_free(x_hat.clone(), val_in_ctx(ctx, &a_out))?,
+            &r.extend(self.arg_name.clone(), x_hat.clone()),
+        )?;
+        Ok((
+            Core::pi(x_hat.clone(), a_out, b_t_out),
+            Core::lambda(x_hat, b_out),
+        ))
+    }
+
+    fn check(&self, ctx: &Ctx, r: &Renaming, tv: &Value) -> errors::Result<Core> {
+        if let Some(pi) = tv.try_as::<Pi<Value, Closure>>() {
+            let a_out = self.arg_type.is_type(ctx, r)?;
+            same_type(ctx, &pi.arg_type, &val_in_ctx(ctx, &a_out))?;
+            Lambda::new(self.arg_name.clone(), self.body.clone()).check(ctx, r, tv)
+        } else {
+            Err(Error::NotAFunctionType(tv.read_back_type(ctx)?))
+        }
+    }
+
+    fn alpha_equiv_aux(
+        &self,
+        other: &dyn CoreInterface,
+        lvl: usize,
+        b1: &alpha::Bindings,
+        b2: &alpha::Bindings,
+    ) -> bool {
+        if let Some(other) = other.as_any().downcast_ref::<Self>() {
+            alpha_equiv_aux(lvl, b1, b2, &self.arg_type, &other.arg_type)
+                && alpha_equiv_aux(
+                    1 + lvl,
+                    &b1.bind(&self.arg_name, lvl),
+                    &b2.bind(&other.arg_name, lvl),
+                    &self.body,
+                    &other.body,
+                )
+        } else {
+            false
+        }
+    }
+
+    fn resugar(&self) -> (HashSet<Symbol>, Core) {
+        let (arg_names, arg_type) = self.arg_type.resugar();
+        let (mut names, body) = self.body.resugar();
+        names.remove(&self.arg_name);
+        (
+            &arg_names | &names,
+            cores::typed_lambda(self.arg_name.clone(), arg_type, body),
+        )
+    }
+}
+
 impl Display for Lambda<Core> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "(λ ({}) {})", self.arg_name.name(), self.body)
@@ -166,3 +242,15 @@ impl ValueInterface for Lambda<Closure> {
         )))
     }
 }
+
+impl Display for TypedLambda {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "(λ (({} {})) {})",
+            self.arg_name.name(),
+            self.arg_type,
+            self.body
+        )
+    }
+}
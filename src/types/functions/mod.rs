@@ -1,15 +1,20 @@
 mod app;
 mod fun;
+mod implicit;
 mod lambda;
 mod pi;
 
-use crate::basics::{Closure, The, Value};
+use crate::basics::{Closure, The, Value, R};
 use crate::types::neutral::Neutral;
+use crate::types::values;
 use crate::types::values::neutral;
 
 pub use app::{App, AppStar, NeutralApp};
 pub use fun::Fun;
-pub use lambda::{Lambda, LambdaStar};
+pub use implicit::{
+    check_with_implicit_lambda, ImplicitApp, ImplicitLambda, ImplicitPi, NeutralImplicitApp,
+};
+pub use lambda::{Lambda, LambdaStar, TypedLambda};
 pub use pi::{Pi, PiStar};
 
 pub fn do_ap(rator: &Value, rand: Value) -> Value {
@@ -25,6 +30,11 @@ pub fn do_ap(rator: &Value, rand: Value) -> Value {
                     pi.res_type.val_of(rand.clone()),
                     NeutralApp(neu.kind.clone(), The(pi.arg_type.clone(), rand)),
                 )
+            } else if let Some(pi) = neu.type_value.try_as::<ImplicitPi<Value, Closure>>() {
+                neutral(
+                    pi.res_type.val_of(rand.clone()),
+                    NeutralImplicitApp(neu.kind.clone(), The(pi.arg_type.clone(), rand)),
+                )
             } else {
                 todo!()
             }
@@ -32,3 +42,34 @@ pub fn do_ap(rator: &Value, rand: Value) -> Value {
         None => todo!("{:?}", rator),
     }
 }
+
+/// Build a function value that collects one argument for each of the first `arity` binders of the
+/// Π type `tv`, and then passes all arguments, together with their types, to `f`.
+pub fn curried(tv: Value, arity: usize, f: R<dyn Sync + Send + Fn(Vec<The>) -> Value>) -> Value {
+    curried_aux(tv, arity, vec![], f)
+}
+
+fn curried_aux(
+    tv: Value,
+    arity: usize,
+    args: Vec<The>,
+    f: R<dyn Sync + Send + Fn(Vec<The>) -> Value>,
+) -> Value {
+    if arity == 0 {
+        return f(args);
+    }
+
+    let pi = tv
+        .try_as::<Pi<Value, Closure>>()
+        .expect("curried functions need a Π type");
+    let arg_type = pi.arg_type.clone();
+    let res_type = pi.res_type.clone();
+    values::lambda(
+        pi.arg_name.clone(),
+        Closure::higher(move |v| {
+            let mut args = args.clone();
+            args.push(The(arg_type.clone(), v.clone()));
+            curried_aux(res_type.val_of(v), arity - 1, args, f.clone())
+        }),
+    )
+}
@@ -54,9 +54,9 @@ impl CoreInterface for Pi<Core, Core> {
     }
 
     fn synth(&self, ctx: &Ctx, r: &Renaming) -> errors::Result<(Core, Core)> {
-        let (x_hat, a_out, b_out) =
+        let (x_hat, a_out, b_out, level) =
             check_with_fresh_binding(ctx, r, &self.arg_name, &self.arg_type, &self.res_type)?;
-        Ok((cores::universe(), Core::pi(x_hat, a_out, b_out)))
+        Ok((cores::universe_level(level), Core::pi(x_hat, a_out, b_out)))
     }
 
     fn alpha_equiv_aux(
@@ -143,9 +143,9 @@ impl CoreInterface for PiStar {
                     res_type: self.res_type.clone(),
                 };
 
-                let (x_hat, a_out, b_out) = check_with_fresh_binding(ctx, r, x, a, &body)?;
+                let (x_hat, a_out, b_out, level) = check_with_fresh_binding(ctx, r, x, a, &body)?;
 
-                Ok((cores::universe(), Core::pi(x_hat, a_out, b_out)))
+                Ok((cores::universe_level(level), Core::pi(x_hat, a_out, b_out)))
             }
         }
     }
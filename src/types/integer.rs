@@ -0,0 +1,348 @@
+use crate::alpha;
+use crate::basics::{
+    Closure, Core, CoreInterface, Ctx, Env, NeutralInterface, Renaming, The, Value, ValueInterface,
+    N,
+};
+use crate::errors::{Error, Result};
+use crate::normalize::{read_back, val_in_ctx};
+use crate::symbol::Symbol;
+use crate::types::functions::do_ap;
+use crate::types::values::later;
+use crate::types::{cores, values};
+use num_bigint::BigInt;
+use std::any::Any;
+use std::collections::HashSet;
+use std::fmt::Formatter;
+
+/// The type of all integers, negative ones included
+#[derive(Debug, Copy, Clone)]
+pub struct Int;
+
+/// An integer written in decimal, such as `-3`. Natural number literals are integers too
+/// when they are checked against `Int`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IntLiteral(pub BigInt);
+
+/// `(succ-Int i)`: the integer one greater than `i`
+#[derive(Debug, Clone, PartialEq)]
+pub struct SuccInt(pub Core);
+
+/// `(pred-Int i)`: the integer one less than `i`
+#[derive(Debug, Clone, PartialEq)]
+pub struct PredInt(pub Core);
+
+/// `(negate-Int i)`
+#[derive(Debug, Clone, PartialEq)]
+pub struct NegateInt(pub Core);
+
+/// `(ind-Int target motive base up down)`: the base is for `0`, and the target is reached by
+/// counting up from it with `up`, if it is positive, or down with `down`, if it is negative.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IndInt {
+    target: Core,
+    motive: Core,
+    base: Core,
+    up: Core,
+    down: Core,
+}
+
+#[derive(Debug)]
+pub struct NeutralSuccInt(pub N);
+
+#[derive(Debug)]
+pub struct NeutralPredInt(pub N);
+
+#[derive(Debug)]
+pub struct NeutralNegateInt(pub N);
+
+#[derive(Debug)]
+pub struct NeutralIndInt(pub N, pub The, pub The, pub The, pub The);
+
+impl IndInt {
+    pub fn new(target: Core, motive: Core, base: Core, up: Core, down: Core) -> Self {
+        IndInt {
+            target,
+            motive,
+            base,
+            up,
+            down,
+        }
+    }
+}
+
+impl CoreInterface for Int {
+    impl_core_defaults!(
+        _,
+        as_any,
+        same,
+        occurring_names,
+        alpha_equiv,
+        simple_type,
+        check_by_synth,
+        (resugar: int)
+    );
+
+    fn val_of(&self, _env: &Env) -> Value {
+        values::int()
+    }
+
+    fn synth(&self, _ctx: &Ctx, _r: &Renaming) -> Result<(Core, Core)> {
+        Ok((cores::universe(), cores::int()))
+    }
+}
+
+impl CoreInterface for IntLiteral {
+    impl_core_defaults!((0), as_any, same, no_type, check_by_synth);
+
+    fn occurring_names(&self) -> HashSet<Symbol> {
+        HashSet::new()
+    }
+
+    fn val_of(&self, _env: &Env) -> Value {
+        Value::new(self.clone())
+    }
+
+    fn synth(&self, _ctx: &Ctx, _r: &Renaming) -> Result<(Core, Core)> {
+        Ok((cores::int(), Core::new(self.clone())))
+    }
+
+    fn alpha_equiv_aux(
+        &self,
+        other: &dyn CoreInterface,
+        _lvl: usize,
+        _b1: &alpha::Bindings,
+        _b2: &alpha::Bindings,
+    ) -> bool {
+        CoreInterface::same(self, other)
+    }
+
+    fn resugar(&self) -> (HashSet<Symbol>, Core) {
+        (HashSet::new(), Core::new(self.clone()))
+    }
+}
+
+macro_rules! impl_int_operation {
+    ($name:ident, $do:ident, $core:ident) => {
+        impl CoreInterface for $name {
+            impl_core_defaults!(
+                (0),
+                as_any,
+                same,
+                occurring_names,
+                alpha_equiv,
+                no_type,
+                check_by_synth,
+                (resugar: $core)
+            );
+
+            fn val_of(&self, env: &Env) -> Value {
+                $do(&later(env.clone(), self.0.clone()))
+            }
+
+            fn synth(&self, ctx: &Ctx, r: &Renaming) -> Result<(Core, Core)> {
+                let i_out = self.0.check(ctx, r, &values::int())?;
+                Ok((cores::int(), cores::$core(i_out)))
+            }
+        }
+    };
+}
+
+impl_int_operation!(SuccInt, do_succ_int, succ_int);
+impl_int_operation!(PredInt, do_pred_int, pred_int);
+impl_int_operation!(NegateInt, do_negate_int, negate_int);
+
+impl CoreInterface for IndInt {
+    impl_core_defaults!(
+        (target, motive, base, up, down),
+        as_any,
+        same,
+        occurring_names,
+        alpha_equiv,
+        no_type,
+        check_by_synth,
+        (resugar: ind_int)
+    );
+
+    fn val_of(&self, env: &Env) -> Value {
+        do_ind_int(
+            &later(env.clone(), self.target.clone()),
+            &later(env.clone(), self.motive.clone()),
+            later(env.clone(), self.base.clone()),
+            &later(env.clone(), self.up.clone()),
+            &later(env.clone(), self.down.clone()),
+        )
+    }
+
+    fn synth(&self, ctx: &Ctx, r: &Renaming) -> Result<(Core, Core)> {
+        let tgt_out = self.target.check(ctx, r, &values::int())?;
+        let mot_out = self.motive.check(ctx, r, &motive_type())?;
+        let mot_val = val_in_ctx(ctx, &mot_out);
+        let b_out = self.base.check(ctx, r, &do_ap(&mot_val, int_value(0)))?;
+        let up_out = self.up.check(ctx, r, &step_type(&mot_val, do_succ_int))?;
+        let down_out = self.down.check(ctx, r, &step_type(&mot_val, do_pred_int))?;
+        Ok((
+            cores::app(mot_out.clone(), tgt_out.clone()),
+            cores::ind_int(tgt_out, mot_out, b_out, up_out, down_out),
+        ))
+    }
+}
+
+impl_sexpr_display!(Int, "Int");
+impl_sexpr_display!(SuccInt, ("succ-Int", 0));
+impl_sexpr_display!(PredInt, ("pred-Int", 0));
+impl_sexpr_display!(NegateInt, ("negate-Int", 0));
+impl_sexpr_display!(IndInt, ("ind-Int", target, motive, base, up, down));
+
+impl std::fmt::Display for IntLiteral {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl ValueInterface for Int {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn same(&self, other: &dyn ValueInterface) -> bool {
+        other.as_any().is::<Self>()
+    }
+
+    fn read_back_type(&self, _ctx: &Ctx) -> Result<Core> {
+        Ok(cores::int())
+    }
+
+    fn read_back(&self, _ctx: &Ctx, tv: &Value, v: &Value) -> Result<Core> {
+        match v.try_as::<IntLiteral>() {
+            Some(IntLiteral(i)) => Ok(cores::int_literal(i.clone())),
+            None => Err(Error::TypeMismatchVar(v.clone(), tv.clone())),
+        }
+    }
+}
+
+impl ValueInterface for IntLiteral {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn same(&self, other: &dyn ValueInterface) -> bool {
+        if let Some(other) = other.try_as::<Self>() {
+            self == other
+        } else {
+            false
+        }
+    }
+
+    fn read_back_type(&self, _ctx: &Ctx) -> Result<Core> {
+        Err(Error::NotATypeVar(Value::new(self.clone())))
+    }
+}
+
+fn int_value(i: impl Into<BigInt>) -> Value {
+    values::int_literal(i.into())
+}
+
+fn motive_type() -> Value {
+    pi_type!(((_i, values::int())), values::universe())
+}
+
+/// The type of a step from `i` to `next(i)`: `(Π ((i Int)) (-> (mot i) (mot (next i))))`
+fn step_type(mot: &Value, next: fn(&Value) -> Value) -> Value {
+    let mot = mot.clone();
+    values::pi(
+        "i",
+        values::int(),
+        Closure::higher(move |i| {
+            let mot = mot.clone();
+            pi_type!(((_ih, do_ap(&mot, i.clone()))), do_ap(&mot, next(&i)))
+        }),
+    )
+}
+
+fn do_int_operation(i: &Value, op: fn(&BigInt) -> BigInt, neutral: fn(N) -> N) -> Value {
+    if let Some(IntLiteral(k)) = i.try_as::<IntLiteral>() {
+        return int_value(op(k));
+    }
+
+    if let Some((_, ne)) = i.as_neutral() {
+        return values::neutral(values::int(), neutral(ne.clone()));
+    }
+
+    unreachable!("{:?}", i)
+}
+
+fn do_succ_int(i: &Value) -> Value {
+    do_int_operation(i, |k| k + 1, |ne| NeutralSuccInt(ne).into())
+}
+
+fn do_pred_int(i: &Value) -> Value {
+    do_int_operation(i, |k| k - 1, |ne| NeutralPredInt(ne).into())
+}
+
+fn do_negate_int(i: &Value) -> Value {
+    do_int_operation(i, |k| -k, |ne| NeutralNegateInt(ne).into())
+}
+
+fn do_ind_int(tgt: &Value, mot: &Value, base: Value, up: &Value, down: &Value) -> Value {
+    // count towards the target from zero, one step at a time
+    if let Some(IntLiteral(k)) = tgt.try_as::<IntLiteral>() {
+        let mut i = BigInt::from(0);
+        let mut result = base;
+        while &i < k {
+            result = do_ap(&do_ap(up, int_value(i.clone())), result);
+            i += 1;
+        }
+        while &i > k {
+            result = do_ap(&do_ap(down, int_value(i.clone())), result);
+            i -= 1;
+        }
+        return result;
+    }
+
+    if let Some((_, ne)) = tgt.as_neutral() {
+        return values::neutral(
+            do_ap(mot, tgt.clone()),
+            NeutralIndInt(
+                ne.clone(),
+                The(motive_type(), mot.clone()),
+                The(do_ap(mot, int_value(0)), base),
+                The(step_type(mot, do_succ_int), up.clone()),
+                The(step_type(mot, do_pred_int), down.clone()),
+            ),
+        );
+    }
+
+    unreachable!("{:?}", tgt)
+}
+
+impl NeutralInterface for NeutralSuccInt {
+    fn read_back_neutral(&self, ctx: &Ctx) -> Result<Core> {
+        Ok(cores::succ_int(self.0.read_back_neutral(ctx)?))
+    }
+}
+
+impl NeutralInterface for NeutralPredInt {
+    fn read_back_neutral(&self, ctx: &Ctx) -> Result<Core> {
+        Ok(cores::pred_int(self.0.read_back_neutral(ctx)?))
+    }
+}
+
+impl NeutralInterface for NeutralNegateInt {
+    fn read_back_neutral(&self, ctx: &Ctx) -> Result<Core> {
+        Ok(cores::negate_int(self.0.read_back_neutral(ctx)?))
+    }
+}
+
+impl NeutralInterface for NeutralIndInt {
+    fn read_back_neutral(&self, ctx: &Ctx) -> Result<Core> {
+        let NeutralIndInt(tgt, The(mot_t, mot), The(b_t, b), The(up_t, up), The(down_t, down)) =
+            self;
+        Ok(cores::ind_int(
+            tgt.read_back_neutral(ctx)?,
+            read_back(ctx, mot_t, mot)?,
+            read_back(ctx, b_t, b)?,
+            read_back(ctx, up_t, up)?,
+            read_back(ctx, down_t, down)?,
+        ))
+    }
+}
@@ -0,0 +1,184 @@
+use crate::alpha::{alpha_equiv_aux, Bindings};
+use crate::basics::{Core, CoreInterface, Ctx, Env, Renaming, Value};
+use crate::errors::Result;
+use crate::normalize::val_in_ctx;
+use crate::symbol::Symbol;
+use crate::types::values::later;
+use crate::{alpha, errors};
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+
+/// Local definitions: `(let ((x e) ...) body)`.
+///
+/// All bound expressions are checked in the surrounding scope, so the bindings are parallel.
+/// The names are transparent in the body, which sees their values rather than neutral variables.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Let {
+    pub bindings: Vec<(Symbol, Core)>,
+    pub body: Core,
+}
+
+/// The scope of a `let`'s body, and the elaborated bindings
+struct Scope {
+    ctx: Ctx,
+    r: Renaming,
+    bindings: Vec<(Symbol, Core)>,
+}
+
+impl Let {
+    /// Synthesize the bound expressions and extend the context with their definitions
+    fn bind(&self, ctx: &Ctx, r: &Renaming) -> Result<Scope> {
+        let mut inner_ctx = ctx.clone();
+        let mut inner_r = r.clone();
+        let mut bindings_out = vec![];
+        for (x, e) in &self.bindings {
+            let (t_out, e_out) = e.synth(ctx, r)?;
+            let x_hat = inner_ctx.fresh(x);
+            inner_ctx = inner_ctx.bind_val(
+                x_hat.clone(),
+                val_in_ctx(ctx, &t_out),
+                val_in_ctx(ctx, &e_out),
+            )?;
+            inner_r = inner_r.extend(x.clone(), x_hat.clone());
+            bindings_out.push((x_hat, e_out));
+        }
+        Ok(Scope {
+            ctx: inner_ctx,
+            r: inner_r,
+            bindings: bindings_out,
+        })
+    }
+}
+
+impl CoreInterface for Let {
+    impl_core_defaults!((bindings, body), as_any, same);
+
+    fn occurring_names(&self) -> HashSet<Symbol> {
+        let mut names = self.body.occurring_names();
+        for (x, e) in &self.bindings {
+            names.insert(x.clone());
+            names.extend(e.occurring_names());
+        }
+        names
+    }
+
+    fn val_of(&self, env: &Env) -> Value {
+        let mut body_env = env.clone();
+        for (x, e) in &self.bindings {
+            body_env = body_env.extend(x.clone(), later(env.clone(), e.clone()));
+        }
+        self.body.val_of(&body_env)
+    }
+
+    fn is_type(&self, ctx: &Ctx, r: &Renaming) -> errors::Result<Core> {
+        let Scope {
+            ctx: body_ctx,
+            r: body_r,
+            bindings,
+        } = self.bind(ctx, r)?;
+        let body = self.body.is_type(&body_ctx, &body_r)?;
+        Ok(Core::new(Let { bindings, body }))
+    }
+
+    fn synth(&self, ctx: &Ctx, r: &Renaming) -> errors::Result<(Core, Core)> {
+        let Scope {
+            ctx: body_ctx,
+            r: body_r,
+            bindings,
+        } = self.bind(ctx, r)?;
+        let (t_out, body) = self.body.synth(&body_ctx, &body_r)?;
+        // the body's type may mention the local names, which are not in scope outside
+        let t_out = val_in_ctx(&body_ctx, &t_out).read_back_type(ctx)?;
+        Ok((t_out, Core::new(Let { bindings, body })))
+    }
+
+    fn check(&self, ctx: &Ctx, r: &Renaming, tv: &Value) -> errors::Result<Core> {
+        let Scope {
+            ctx: body_ctx,
+            r: body_r,
+            bindings,
+        } = self.bind(ctx, r)?;
+        let body = self.body.check(&body_ctx, &body_r, tv)?;
+        Ok(Core::new(Let { bindings, body }))
+    }
+
+    fn alpha_equiv_aux(
+        &self,
+        other: &dyn CoreInterface,
+        lvl: usize,
+        b1: &alpha::Bindings,
+        b2: &alpha::Bindings,
+    ) -> bool {
+        match other.as_any().downcast_ref::<Self>() {
+            Some(other) if self.bindings.len() == other.bindings.len() => {
+                let values_equiv = self
+                    .bindings
+                    .iter()
+                    .zip(&other.bindings)
+                    .all(|((_, e1), (_, e2))| alpha_equiv_aux(lvl, b1, b2, e1, e2));
+                values_equiv
+                    && bodies_equiv(
+                        &self.bindings,
+                        &other.bindings,
+                        &self.body,
+                        &other.body,
+                        lvl,
+                        b1,
+                        b2,
+                    )
+            }
+            _ => false,
+        }
+    }
+
+    fn resugar(&self) -> (HashSet<Symbol>, Core) {
+        let (mut names, body) = self.body.resugar();
+        let mut bindings = vec![];
+        for (x, _) in &self.bindings {
+            names.remove(x);
+        }
+        for (x, e) in &self.bindings {
+            let (e_names, e) = e.resugar();
+            names.extend(e_names);
+            bindings.push((x.clone(), e));
+        }
+        (names, Core::new(Let { bindings, body }))
+    }
+}
+
+/// Compare the bodies with both sides' names bound at the same levels
+fn bodies_equiv(
+    bs1: &[(Symbol, Core)],
+    bs2: &[(Symbol, Core)],
+    body1: &Core,
+    body2: &Core,
+    lvl: usize,
+    b1: &Bindings,
+    b2: &Bindings,
+) -> bool {
+    match (bs1, bs2) {
+        ([(x, _), rest1 @ ..], [(y, _), rest2 @ ..]) => bodies_equiv(
+            rest1,
+            rest2,
+            body1,
+            body2,
+            lvl + 1,
+            &b1.bind(x, lvl),
+            &b2.bind(y, lvl),
+        ),
+        _ => alpha_equiv_aux(lvl, b1, b2, body1, body2),
+    }
+}
+
+impl Display for Let {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "(let (")?;
+        for (i, (x, e)) in self.bindings.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "({} {})", x.name(), e)?;
+        }
+        write!(f, ") {})", self.body)
+    }
+}
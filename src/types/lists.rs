@@ -7,7 +7,7 @@ use crate::normalize::{read_back, val_in_ctx};
 use crate::symbol::Symbol;
 use crate::types::functions::do_ap;
 use crate::types::values::later;
-use crate::types::{cores, values, MaybeTyped};
+use crate::types::{cores, synth_type_level, values, MaybeTyped};
 use std::any::Any;
 use std::collections::HashSet;
 
@@ -60,8 +60,8 @@ impl CoreInterface for List<Core> {
     }
 
     fn synth(&self, ctx: &Ctx, r: &Renaming) -> Result<(Core, Core)> {
-        let e_out = self.0.check(ctx, r, &values::universe())?;
-        Ok((cores::universe(), cores::list(e_out)))
+        let (e_out, level) = synth_type_level(ctx, r, &self.0)?;
+        Ok((cores::universe_level(level), cores::list(e_out)))
     }
 }
 
@@ -101,7 +101,6 @@ impl CoreInterface for ListCons<Core> {
         occurring_names,
         alpha_equiv,
         no_type,
-        check_by_synth,
         (resugar: list_cons)
     );
 
@@ -118,6 +117,16 @@ impl CoreInterface for ListCons<Core> {
         let es_out = self.1.check(ctx, r, &val_in_ctx(ctx, &lt))?;
         Ok((lt, cores::list_cons(e_out, es_out)))
     }
+
+    fn check(&self, ctx: &Ctx, r: &Renaming, tv: &Value) -> Result<Core> {
+        if let Some(List(etv)) = tv.try_as::<List<Value>>() {
+            let e_out = self.0.check(ctx, r, etv)?;
+            let es_out = self.1.check(ctx, r, tv)?;
+            Ok(cores::list_cons(e_out, es_out))
+        } else {
+            Err(Error::NotAListType(tv.read_back_type(ctx)?))
+        }
+    }
 }
 
 fn synth_rec_list(this: &RecList, ctx: &Ctx, r: &Renaming, b: &Core) -> Result<(Core, Core)> {
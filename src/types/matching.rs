@@ -0,0 +1,320 @@
+use crate::basics::{Core, CoreInterface, Ctx, Env, Renaming, Value};
+use crate::errors::{Error, Result};
+use crate::normalize::{read_back, val_in_ctx};
+use crate::symbol::Symbol;
+use crate::types::absurd::Absurd;
+use crate::types::either::Either;
+use crate::types::lists::List;
+use crate::types::motive::{fresh, motive_binder};
+use crate::types::natural::Nat;
+use crate::types::reference::Ref;
+use crate::types::vec::Vector;
+use crate::types::{cores, values};
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+
+/// Case analysis with one clause per constructor; elaborates to the matching `ind-*` eliminator.
+///
+/// The motive is taken from the type the match is checked against. If the target is a
+/// variable, occurrences of that variable in the expected type are abstracted over, so
+/// each clause's body is checked against the expected type specialized to its constructor.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Match {
+    pub target: Core,
+    pub clauses: Vec<Clause>,
+}
+
+/// A clause `((ctor fields... ih...) body)`; inductive hypotheses may be left out
+#[derive(Debug, Clone, PartialEq)]
+pub struct Clause {
+    pub ctor: Symbol,
+    pub binders: Vec<Symbol>,
+    pub body: Core,
+}
+
+impl CoreInterface for Match {
+    impl_core_defaults!((target, clauses), as_any, same, no_type, no_alpha_equiv);
+
+    fn occurring_names(&self) -> HashSet<Symbol> {
+        let mut names = self.target.occurring_names();
+        for clause in &self.clauses {
+            names.extend(clause.binders.iter().cloned());
+            names.extend(clause.body.occurring_names());
+        }
+        names
+    }
+
+    fn val_of(&self, _env: &Env) -> Value {
+        unimplemented!("evaluate an elaborated match instead")
+    }
+
+    fn synth(&self, _ctx: &Ctx, _r: &Renaming) -> Result<(Core, Core)> {
+        Err(Error::CantDetermineType(Core::new(self.clone())))
+    }
+
+    fn check(&self, ctx: &Ctx, r: &Renaming, tv: &Value) -> Result<Core> {
+        let (tgt_t, _) = self.target.synth(ctx, r)?;
+        let tgt_tv = val_in_ctx(ctx, &tgt_t);
+        let t = tv.read_back_type(ctx)?;
+
+        let mut used = &(&ctx.names_only() | &t.occurring_names()) | &self.occurring_names();
+        let x = motive_binder(r, &self.target, &mut used, "x");
+
+        let elaborated = if tgt_tv.try_as::<Nat>().is_some() {
+            let [base, step] = self.arrange(&tgt_t, &mut used, [("zero", 0, 0), ("add1", 1, 1)])?;
+            cores::ind_nat(self.target.clone(), motive(x, t), base, step)
+        } else if tgt_tv.try_as::<List<Value>>().is_some() {
+            let [base, step] = self.arrange(&tgt_t, &mut used, [("nil", 0, 0), ("::", 2, 1)])?;
+            cores::ind_list(self.target.clone(), motive(x, t), base, step)
+        } else if let Some(Vector(_, len)) = tgt_tv.try_as::<Vector<Value>>() {
+            let len = read_back(ctx, &values::nat(), len)?;
+            let k = match len.try_as::<Ref>() {
+                Some(k) => k.name().clone(),
+                None => fresh(&mut used, "k"),
+            };
+            let [base, step] =
+                self.arrange(&tgt_t, &mut used, [("vecnil", 0, 0), ("vec::", 2, 1)])?;
+            let step = Core::lambda(fresh(&mut used, "k"), step);
+            let motive = cores::resolved(Core::lambda_star(vec![k, x], t));
+            cores::ind_vec(
+                cores::resolved(len),
+                self.target.clone(),
+                motive,
+                base,
+                step,
+            )
+        } else if tgt_tv.try_as::<Either<Value>>().is_some() {
+            let [l, r] = self.arrange(&tgt_t, &mut used, [("left", 1, 0), ("right", 1, 0)])?;
+            cores::ind_either(self.target.clone(), motive(x, t), l, r)
+        } else if tgt_tv.try_as::<Absurd>().is_some() {
+            let [] = self.arrange(&tgt_t, &mut used, [])?;
+            cores::ind_absurd(self.target.clone(), cores::resolved(t))
+        } else {
+            return Err(Error::CantMatchOn(tgt_t));
+        };
+
+        elaborated.check(ctx, r, tv)
+    }
+
+    fn resugar(&self) -> (HashSet<Symbol>, Core) {
+        unimplemented!("resugar an elaborated match instead")
+    }
+}
+
+impl Match {
+    /// Order the clauses like the eliminator's methods, and turn each into a method.
+    /// Constructors are given as (name, number of fields, number of inductive hypotheses).
+    fn arrange<const N: usize>(
+        &self,
+        tgt_t: &Core,
+        used: &mut HashSet<Symbol>,
+        ctors: [(&str, usize, usize); N],
+    ) -> Result<[Core; N]> {
+        for (i, clause) in self.clauses.iter().enumerate() {
+            if !ctors.iter().any(|(c, _, _)| clause.ctor == *c) {
+                return Err(Error::NotAConstructorOf(clause.ctor.clone(), tgt_t.clone()));
+            }
+            if self.clauses[..i].iter().any(|c| c.ctor == clause.ctor) {
+                return Err(Error::DuplicateClause(clause.ctor.clone()));
+            }
+        }
+
+        let mut methods = vec![];
+        for (ctor, n_fields, n_ihs) in ctors {
+            let clause = match self.clauses.iter().find(|c| c.ctor == ctor) {
+                Some(clause) => clause,
+                None => return Err(Error::MissingClause(ctor.into())),
+            };
+
+            let mut binders = clause.binders.clone();
+            if binders.len() != n_fields && binders.len() != n_fields + n_ihs {
+                return Err(Error::WrongPatternArity(clause.ctor.clone(), n_fields));
+            }
+            while binders.len() < n_fields + n_ihs {
+                binders.push(fresh(used, "ih"));
+            }
+
+            methods.push(if binders.is_empty() {
+                clause.body.clone()
+            } else {
+                Core::lambda_star(binders, clause.body.clone())
+            });
+        }
+        Ok(methods.try_into().unwrap())
+    }
+}
+
+/// The motive `(λ (x) t)`, whose names are those of the context
+fn motive(x: Symbol, t: Core) -> Core {
+    cores::resolved(Core::lambda(x, t))
+}
+
+impl Display for Match {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "(match {}", self.target)?;
+        for clause in &self.clauses {
+            write!(f, " {}", clause)?;
+        }
+        write!(f, ")")
+    }
+}
+
+impl Display for Clause {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.binders.is_empty() {
+            write!(f, "({} {})", self.ctor.name(), self.body)
+        } else {
+            write!(f, "(({}", self.ctor.name())?;
+            for x in &self.binders {
+                write!(f, " {}", x.name())?;
+            }
+            write!(f, ") {})", self.body)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::basics::{Core, Ctx};
+    use crate::errors::Error;
+    use crate::rep::{eval_normalize, norm};
+
+    fn define(ctx: &mut Ctx, name: &str, t: &str, v: &str) -> Result<(), String> {
+        eval_normalize(ctx, &format!("(claim {} {})", name, t))?;
+        eval_normalize(ctx, &format!("(define {} {})", name, v))?;
+        Ok(())
+    }
+
+    fn evaluates_to(ctx: &Ctx, e: &str, t: &str, v: &str) {
+        assert_eq!(
+            norm(ctx, &e.parse().unwrap()),
+            Ok(Core::the(t.parse().unwrap(), v.parse().unwrap()))
+        );
+    }
+
+    #[test]
+    fn match_on_nat() {
+        let mut ctx = Ctx::new();
+        define(
+            &mut ctx,
+            "pred",
+            "(-> Nat Nat)",
+            "(λ (n) (match n (zero 0) ((add1 n-1) n-1)))",
+        )
+        .unwrap();
+        evaluates_to(&ctx, "(pred 5)", "Nat", "4");
+        evaluates_to(&ctx, "(pred 0)", "Nat", "0");
+    }
+
+    #[test]
+    fn match_is_dependent_on_a_variable_target() {
+        let mut ctx = Ctx::new();
+        define(
+            &mut ctx,
+            "count-up",
+            "(Π ((n Nat)) (= Nat (iter-Nat n 0 (λ (k) (add1 k))) n))",
+            "(λ (n)
+               (match n
+                 (zero (same zero))
+                 ((add1 n-1 ih) (cong ih (the (-> Nat Nat) (λ (k) (add1 k)))))))",
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn match_on_list_with_inductive_hypothesis() {
+        let mut ctx = Ctx::new();
+        define(
+            &mut ctx,
+            "length",
+            "(Π ((E U)) (-> (List E) Nat))",
+            "(λ (E es) (match es (nil 0) ((:: e es ih) (add1 ih))))",
+        )
+        .unwrap();
+        evaluates_to(&ctx, "(length Atom (:: 'a (:: 'b nil)))", "Nat", "2");
+    }
+
+    #[test]
+    fn match_on_vec() {
+        let mut ctx = Ctx::new();
+        define(
+            &mut ctx,
+            "empty?",
+            "(Π ((n Nat)) (-> (Vec Atom n) Atom))",
+            "(λ (n es) (match es (vecnil 'yes) ((vec:: e es) 'no)))",
+        )
+        .unwrap();
+        evaluates_to(&ctx, "(empty? 0 vecnil)", "Atom", "'yes");
+        evaluates_to(&ctx, "(empty? 1 (vec:: 'a vecnil))", "Atom", "'no");
+    }
+
+    #[test]
+    fn match_on_either_and_absurd() {
+        let mut ctx = Ctx::new();
+        define(
+            &mut ctx,
+            "either-nat",
+            "(-> (Either Nat Nat) Nat)",
+            "(λ (p) (match p ((left x) x) ((right y) (add1 y))))",
+        )
+        .unwrap();
+        define(&mut ctx, "anything", "(-> Absurd Nat)", "(λ (x) (match x))").unwrap();
+        evaluates_to(
+            &ctx,
+            "(either-nat (the (Either Nat Nat) (right 1)))",
+            "Nat",
+            "2",
+        );
+    }
+
+    #[test]
+    fn clauses_must_cover_every_constructor_once() {
+        let mut ctx = Ctx::new();
+        eval_normalize(&mut ctx, "(claim f (-> Nat Nat))").unwrap();
+
+        let define_f = |ctx: &mut Ctx, body: &str| {
+            eval_normalize(ctx, &format!("(define f (λ (n) {}))", body))
+        };
+        assert_eq!(
+            define_f(&mut ctx, "(match n (zero 0))"),
+            Err(Error::MissingClause("add1".into()).to_string())
+        );
+        assert_eq!(
+            define_f(&mut ctx, "(match n (zero 0) (zero 1) ((add1 k) k))"),
+            Err(Error::DuplicateClause("zero".into()).to_string())
+        );
+        assert_eq!(
+            define_f(&mut ctx, "(match n (zero 0) (nil 0) ((add1 k) k))"),
+            Err(Error::NotAConstructorOf("nil".into(), "Nat".parse().unwrap()).to_string())
+        );
+        assert_eq!(
+            define_f(&mut ctx, "(match n (zero 0) ((add1) 0))"),
+            Err(Error::WrongPatternArity("add1".into(), 1).to_string())
+        );
+    }
+
+    #[test]
+    fn malformed_clauses_are_syntax_errors() {
+        let mut ctx = Ctx::new();
+        assert_eq!(
+            eval_normalize(&mut ctx, "(the Nat (match 2 zero))"),
+            Err(Error::InvalidSyntax("(match 2 zero)".into()).to_string())
+        );
+        assert_eq!(
+            eval_normalize(&mut ctx, "(match 2 (() 0))"),
+            Err(Error::InvalidSyntax("(match 2 (() 0))".into()).to_string())
+        );
+    }
+
+    #[test]
+    fn matches_need_a_type_to_be_checked_against() {
+        let mut ctx = Ctx::new();
+        assert_eq!(
+            eval_normalize(&mut ctx, "(match 3 (zero 'z) ((add1 k) 'nz))"),
+            Err(
+                Error::CantDetermineType("(match 3 (zero 'z) ((add1 k) 'nz))".parse().unwrap())
+                    .to_string()
+            )
+        );
+    }
+}
@@ -0,0 +1,113 @@
+use crate::basics::{Core, CoreInterface, Ctx, Env, NeutralInterface, Renaming, Value, R};
+use crate::errors::{Error, Result};
+use crate::symbol::Symbol;
+use crate::unify::MetaVar;
+use crate::{alpha, errors};
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+
+/// A metavariable: an unknown expression that is solved by unification.
+/// Once solved, it behaves exactly like its solution.
+#[derive(Debug, Clone)]
+pub struct Meta(pub R<MetaVar>);
+
+/// An unsolved metavariable as a value
+#[derive(Debug)]
+pub struct NeutralMeta(pub R<MetaVar>);
+
+impl PartialEq for Meta {
+    fn eq(&self, other: &Self) -> bool {
+        R::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl CoreInterface for Meta {
+    impl_core_defaults!((0), as_any, same);
+
+    fn occurring_names(&self) -> HashSet<Symbol> {
+        match self.0.solution() {
+            Some(e) => e.occurring_names(),
+            None => [self.0.key.clone()].into_iter().collect(),
+        }
+    }
+
+    fn val_of(&self, env: &Env) -> Value {
+        match self.0.solution() {
+            Some(e) => e.val_of(env),
+            None => self.0.as_value(),
+        }
+    }
+
+    fn is_type(&self, ctx: &Ctx, r: &Renaming) -> errors::Result<Core> {
+        match self.0.solution() {
+            Some(e) => e.is_type(ctx, r),
+            None => Err(self.unsolved()),
+        }
+    }
+
+    fn synth(&self, ctx: &Ctx, r: &Renaming) -> errors::Result<(Core, Core)> {
+        match self.0.solution() {
+            Some(e) => e.synth(ctx, r),
+            None => Err(self.unsolved()),
+        }
+    }
+
+    fn check(&self, ctx: &Ctx, r: &Renaming, tv: &Value) -> errors::Result<Core> {
+        match self.0.solution() {
+            Some(e) => e.check(ctx, r, tv),
+            None => Err(self.unsolved()),
+        }
+    }
+
+    fn alpha_equiv_aux(
+        &self,
+        other: &dyn CoreInterface,
+        lvl: usize,
+        b1: &alpha::Bindings,
+        b2: &alpha::Bindings,
+    ) -> bool {
+        if let Some(e) = self.0.solution() {
+            return e.alpha_equiv_aux(other, lvl, b1, b2);
+        }
+        if let Some(other) = other.try_as::<Meta>() {
+            if self == other {
+                return true;
+            }
+        }
+        match other.as_core() {
+            Some(e) => self.0.solve(e, b2),
+            None => false,
+        }
+    }
+
+    fn resugar(&self) -> (HashSet<Symbol>, Core) {
+        match self.0.solution() {
+            Some(e) => e.resugar(),
+            None => (HashSet::new(), Core::new(self.clone())),
+        }
+    }
+}
+
+impl Meta {
+    fn unsolved(&self) -> Error {
+        Error::UnsolvedMeta(self.0.name.clone(), Core::new(self.clone()))
+    }
+}
+
+impl Display for Meta {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.0.solution() {
+            Some(e) => write!(f, "{}", e),
+            None => write!(f, "?{}", self.0.name.name()),
+        }
+    }
+}
+
+impl NeutralInterface for NeutralMeta {
+    fn read_back_neutral(&self, _ctx: &Ctx) -> Result<Core> {
+        Ok(self
+            .0
+            .solution()
+            .unwrap_or_else(|| Core::new(Meta(self.0.clone()))))
+    }
+}
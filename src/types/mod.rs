@@ -1,6 +1,8 @@
 use crate::basics::{Core, CoreInterface, Ctx, Renaming};
+use crate::errors::Error;
 use crate::normalize::val_in_ctx;
 use crate::symbol::Symbol;
+use crate::types::universe::Universe;
 use crate::{alpha, errors};
 use std::collections::HashSet;
 macro_rules! pi_type {
@@ -242,8 +244,7 @@ macro_rules! ternary_eliminator {
                 same,
                 occurring_names,
                 alpha_equiv,
-                no_type,
-                check_by_synth
+                no_type
             );
 
             fn val_of(&self, env: &Env) -> Value {
@@ -266,11 +267,28 @@ macro_rules! ternary_eliminator {
 
             fn synth(&self, ctx: &Ctx, r: &Renaming) -> crate::errors::Result<(Core, Core)> {
                 match &self.base {
-                    MaybeTyped::The(_, _) => unimplemented!("already synth'ed"),
+                    MaybeTyped::The(bt, b) => {
+                        $synth_func(self, ctx, r, &cores::the(bt.clone(), b.clone()))
+                    }
                     MaybeTyped::Plain(b) => $synth_func(self, ctx, r, b),
                 }
             }
 
+            fn check(&self, ctx: &Ctx, r: &Renaming, tv: &Value) -> crate::errors::Result<Core> {
+                // when checked, the base has the type of the whole expression
+                let (t_out, e_out) = match &self.base {
+                    MaybeTyped::Plain(b) => $synth_func(
+                        self,
+                        ctx,
+                        r,
+                        &cores::the(cores::resolved(tv.read_back_type(ctx)?), b.clone()),
+                    )?,
+                    MaybeTyped::The(..) => self.synth(ctx, r)?,
+                };
+                crate::typechecker::same_type(ctx, &crate::normalize::val_in_ctx(ctx, &t_out), tv)?;
+                Ok(e_out)
+            }
+
             fn resugar(&self) -> (HashSet<Symbol>, Core) {
                 let tgt = self.target.resugar();
                 let bas = self.base.resugar();
@@ -291,20 +309,31 @@ macro_rules! ternary_eliminator {
 mod absurd;
 mod annotation;
 mod atom;
+mod case_split;
 pub mod cores;
+pub mod data;
 mod delay;
 mod either;
+mod enumeration;
 mod equality;
 pub mod functions;
+pub mod integer;
 mod invalid;
+mod let_expr;
 mod lists;
+pub mod matching;
+pub mod meta;
+pub mod motive;
 pub mod natural;
 mod neutral;
-mod pairs;
+pub mod pairs;
+pub mod record;
 pub mod reference;
-mod todo;
+mod search;
+mod stream;
+pub mod todo;
 mod trivial;
-mod universe;
+pub mod universe;
 pub mod values;
 mod vec;
 
@@ -325,19 +354,41 @@ fn is_type_with_fresh_binding<T: CoreInterface>(
     Ok((x_hat, a_out, b_out))
 }
 
+/// Like `is_type_with_fresh_binding`, but also returns the universe level the
+/// binder's type and the body live in.
 fn check_with_fresh_binding<T: CoreInterface>(
     ctx: &Ctx,
     r: &Renaming,
     x: &Symbol,
     x_type: &Core,
     body: &T,
-) -> errors::Result<(Symbol, Core, Core)> {
+) -> errors::Result<(Symbol, Core, Core, usize)> {
     let x_hat = ctx.fresh(x);
-    let a_out = x_type.check(ctx, r, &values::universe())?;
+    let (a_out, a_level) = synth_type_level(ctx, r, x_type)?;
     let ctx_hat = ctx.bind_free(x_hat.clone(), val_in_ctx(ctx, &a_out))?;
     let r_hat = r.extend(x.clone(), x_hat.clone());
-    let b_out = body.check(&ctx_hat, &r_hat, &values::universe())?;
-    Ok((x_hat, a_out, b_out))
+    let (b_out, b_level) = synth_type_level(&ctx_hat, &r_hat, body)?;
+    Ok((x_hat, a_out, b_out, a_level.max(b_level)))
+}
+
+/// Elaborate a type expression and find the lowest universe it belongs to.
+/// Expressions that can't be synthesized are checked against `U`.
+fn synth_type_level<T: CoreInterface + ?Sized>(
+    ctx: &Ctx,
+    r: &Renaming,
+    t: &T,
+) -> errors::Result<(Core, usize)> {
+    match t.synth(ctx, r) {
+        Ok((t_t_out, t_out)) => {
+            let t_tv = val_in_ctx(ctx, &t_t_out);
+            match t_tv.try_as::<Universe>() {
+                Some(Universe(level)) => Ok((t_out, *level)),
+                None => Err(Error::WrongType(t_t_out, cores::universe())),
+            }
+        }
+        Err(Error::CantDetermineType(_)) => Ok((t.check(ctx, r, &values::universe())?, 0)),
+        Err(e) => Err(e),
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -0,0 +1,363 @@
+use crate::basics::{Core, CoreInterface, Ctx, Env, Renaming, Value};
+use crate::errors::{Error, Result};
+use crate::fresh::freshen;
+use crate::normalize::{read_back, val_in_ctx};
+use crate::symbol::Symbol;
+use crate::types::cores;
+use crate::types::equality::Equal;
+use crate::types::reference::Ref;
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+
+/// An eliminator written without its motive, such as `(ind-Nat target base step)`.
+///
+/// These forms can only be checked. The motive is the expected type, abstracted over the target
+/// if the target is a variable (for `ind-Vec` also over the length, and for `replace` over the
+/// equality's `to` side).
+#[derive(Debug, Clone, PartialEq)]
+pub struct InferredMotive {
+    pub eliminator: Eliminator,
+    pub args: Vec<Core>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Eliminator {
+    IndNat,
+    IndList,
+    IndVec,
+    IndEither,
+    Replace,
+}
+
+impl Eliminator {
+    fn name(&self) -> &'static str {
+        match self {
+            Eliminator::IndNat => "ind-Nat",
+            Eliminator::IndList => "ind-List",
+            Eliminator::IndVec => "ind-Vec",
+            Eliminator::IndEither => "ind-Either",
+            Eliminator::Replace => "replace",
+        }
+    }
+}
+
+impl CoreInterface for InferredMotive {
+    impl_core_defaults!(
+        (eliminator, args),
+        as_any,
+        same,
+        no_type,
+        no_synth,
+        no_alpha_equiv
+    );
+
+    fn occurring_names(&self) -> HashSet<Symbol> {
+        self.args.iter().flat_map(|a| a.occurring_names()).collect()
+    }
+
+    fn val_of(&self, _env: &Env) -> Value {
+        unimplemented!("evaluate an eliminator with explicit motive instead")
+    }
+
+    fn check(&self, ctx: &Ctx, r: &Renaming, tv: &Value) -> Result<Core> {
+        let t = tv.read_back_type(ctx)?;
+        let mut used = &(&ctx.names_only() | &t.occurring_names()) | &self.occurring_names();
+        let args = self.args.clone();
+
+        let elaborated = match (self.eliminator, &args[..]) {
+            (Eliminator::IndNat, [tgt, base, step]) => {
+                let x = motive_binder(r, tgt, &mut used, "n");
+                let motive = cores::resolved(Core::lambda(x, t));
+                cores::ind_nat(tgt.clone(), motive, base.clone(), step.clone())
+            }
+            (Eliminator::IndList, [tgt, base, step]) => {
+                let x = motive_binder(r, tgt, &mut used, "es");
+                let motive = cores::resolved(Core::lambda(x, t));
+                cores::ind_list(tgt.clone(), motive, base.clone(), step.clone())
+            }
+            (Eliminator::IndVec, [len, tgt, base, step]) => {
+                let k = motive_binder(r, len, &mut used, "k");
+                let x = motive_binder(r, tgt, &mut used, "es");
+                let motive = cores::resolved(Core::lambda_star(vec![k, x], t));
+                cores::ind_vec(len.clone(), tgt.clone(), motive, base.clone(), step.clone())
+            }
+            (Eliminator::IndEither, [tgt, left, right]) => {
+                let x = motive_binder(r, tgt, &mut used, "x");
+                let motive = cores::resolved(Core::lambda(x, t));
+                cores::ind_either(tgt.clone(), motive, left.clone(), right.clone())
+            }
+            (Eliminator::Replace, [tgt, base]) => {
+                let (tgt_t, _) = tgt.synth(ctx, r)?;
+                let tgt_tv = val_in_ctx(ctx, &tgt_t);
+                let to = match tgt_tv.try_as::<Equal<Value>>() {
+                    Some(eq) => read_back(ctx, &eq.typ, &eq.to)?,
+                    None => return Err(Error::NotAnEqualType(tgt_t)),
+                };
+                let x = match to.try_as::<Ref>() {
+                    Some(to) => to.name().clone(),
+                    None => fresh(&mut used, "x"),
+                };
+                let motive = cores::resolved(Core::lambda(x, t));
+                cores::replace(tgt.clone(), motive, base.clone())
+            }
+            _ => return Err(Error::WrongArity(Core::new(self.clone()))),
+        };
+
+        elaborated.check(ctx, r, tv)
+    }
+
+    fn resugar(&self) -> (HashSet<Symbol>, Core) {
+        unimplemented!("resugar an eliminator with explicit motive instead")
+    }
+}
+
+/// The motive's binder for a target, in the context's names; reusing the target's name makes
+/// the motive depend on it.
+pub fn motive_binder(r: &Renaming, target: &Core, used: &mut HashSet<Symbol>, x: &str) -> Symbol {
+    if let Some(target) = target.try_as::<Ref>() {
+        return r.rename(target.name());
+    }
+    match target
+        .try_as::<Resolved>()
+        .and_then(|t| t.0.try_as::<Ref>())
+    {
+        Some(target) => target.name().clone(),
+        None => fresh(used, x),
+    }
+}
+
+/// An expression built from the context, such as a motive read back from the expected type.
+/// Its names are those of the context already, so the renaming of the surrounding source
+/// expression does not apply to it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Resolved(pub Core);
+
+impl CoreInterface for Resolved {
+    impl_core_defaults!((0), as_any, same, occurring_names, alpha_equiv);
+
+    fn val_of(&self, env: &Env) -> Value {
+        self.0.val_of(env)
+    }
+
+    fn is_type(&self, ctx: &Ctx, _r: &Renaming) -> Result<Core> {
+        self.0.is_type(ctx, &Renaming::new())
+    }
+
+    fn synth(&self, ctx: &Ctx, _r: &Renaming) -> Result<(Core, Core)> {
+        self.0.synth(ctx, &Renaming::new())
+    }
+
+    fn check(&self, ctx: &Ctx, _r: &Renaming, tv: &Value) -> Result<Core> {
+        self.0.check(ctx, &Renaming::new(), tv)
+    }
+
+    fn resugar(&self) -> (HashSet<Symbol>, Core) {
+        self.0.resugar()
+    }
+}
+
+impl Display for Resolved {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+pub fn fresh(used: &mut HashSet<Symbol>, x: &str) -> Symbol {
+    let x = freshen(used, &x.into());
+    used.insert(x.clone());
+    x
+}
+
+impl Display for InferredMotive {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}", self.eliminator.name())?;
+        for arg in &self.args {
+            write!(f, " {}", arg)?;
+        }
+        write!(f, ")")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::basics::{Core, Ctx};
+    use crate::errors::Error;
+    use crate::rep::{eval_normalize, eval_with_goals, norm};
+
+    fn define(ctx: &mut Ctx, name: &str, t: &str, v: &str) -> Result<(), String> {
+        eval_normalize(ctx, &format!("(claim {} {})", name, t))?;
+        eval_normalize(ctx, &format!("(define {} {})", name, v))?;
+        Ok(())
+    }
+
+    fn evaluates_to(ctx: &Ctx, e: &str, t: &str, v: &str) {
+        assert_eq!(
+            norm(ctx, &e.parse().unwrap()),
+            Ok(Core::the(t.parse().unwrap(), v.parse().unwrap()))
+        );
+    }
+
+    #[test]
+    fn bases_of_non_dependent_eliminators_get_the_expected_type() {
+        let mut ctx = Ctx::new();
+        define(
+            &mut ctx,
+            "firsts",
+            "(-> Nat (List Atom))",
+            "(λ (n) (iter-Nat n nil (λ (es) (:: 'a es))))",
+        )
+        .unwrap();
+        define(
+            &mut ctx,
+            "count",
+            "(-> (List Atom) (List Nat))",
+            "(λ (es) (rec-List es nil (λ (e es ns) (:: 0 ns))))",
+        )
+        .unwrap();
+        define(
+            &mut ctx,
+            "down",
+            "(-> Nat (List Nat))",
+            "(λ (n) (rec-Nat n nil (λ (k ks) (:: k ks))))",
+        )
+        .unwrap();
+        define(
+            &mut ctx,
+            "empty-if-zero",
+            "(-> Nat (List Nat))",
+            "(λ (n) (which-Nat n nil (λ (k) (:: k nil))))",
+        )
+        .unwrap();
+        evaluates_to(&ctx, "(firsts 2)", "(List Atom)", "(:: 'a (:: 'a nil))");
+        evaluates_to(&ctx, "(count (:: 'x nil))", "(List Nat)", "(:: 0 nil)");
+        evaluates_to(&ctx, "(down 2)", "(List Nat)", "(:: 1 (:: 0 nil))");
+        evaluates_to(&ctx, "(empty-if-zero 3)", "(List Nat)", "(:: 2 nil)");
+    }
+
+    #[test]
+    fn motives_are_abstracted_from_the_expected_type() {
+        let mut ctx = Ctx::new();
+        define(
+            &mut ctx,
+            "count-up",
+            "(Π ((n Nat)) (= Nat (iter-Nat n 0 (λ (k) (add1 k))) n))",
+            "(λ (n) (ind-Nat n (same zero) (λ (n-1 ih) (cong ih (the (-> Nat Nat) (λ (k) (add1 k)))))))",
+        )
+        .unwrap();
+        define(
+            &mut ctx,
+            "copies",
+            "(Π ((n Nat)) (Vec Atom n))",
+            "(λ (n) (ind-Nat n vecnil (λ (k es) (vec:: 'a es))))",
+        )
+        .unwrap();
+        define(
+            &mut ctx,
+            "vec->list",
+            "(Π ((n Nat)) (-> (Vec Atom n) (List Atom)))",
+            "(λ (n es) (ind-Vec n es nil (λ (k e es rest) (:: e rest))))",
+        )
+        .unwrap();
+        define(
+            &mut ctx,
+            "list-length",
+            "(-> (List Atom) Nat)",
+            "(λ (es) (ind-List es 0 (λ (e es n) (add1 n))))",
+        )
+        .unwrap();
+        define(
+            &mut ctx,
+            "either->nat",
+            "(-> (Either Nat Atom) Nat)",
+            "(λ (x) (ind-Either x (λ (n) n) (λ (a) 0)))",
+        )
+        .unwrap();
+        evaluates_to(
+            &ctx,
+            "(copies 2)",
+            "(Vec Atom 2)",
+            "(vec:: 'a (vec:: 'a vecnil))",
+        );
+        evaluates_to(
+            &ctx,
+            "(vec->list 2 (copies 2))",
+            "(List Atom)",
+            "(:: 'a (:: 'a nil))",
+        );
+        evaluates_to(&ctx, "(list-length (:: 'a nil))", "Nat", "1");
+        evaluates_to(
+            &ctx,
+            "(either->nat (the (Either Nat Atom) (left 3)))",
+            "Nat",
+            "3",
+        );
+    }
+
+    #[test]
+    fn replace_abstracts_over_the_to_side() {
+        let mut ctx = Ctx::new();
+        define(
+            &mut ctx,
+            "transport",
+            "(Π ((a Nat) (b Nat)) (-> (= Nat a b) (Vec Atom a) (Vec Atom b)))",
+            "(λ (a b a=b es) (replace a=b es))",
+        )
+        .unwrap();
+        evaluates_to(
+            &ctx,
+            "(transport 1 1 (same 1) (vec:: 'x vecnil))",
+            "(Vec Atom 1)",
+            "(vec:: 'x vecnil)",
+        );
+    }
+
+    #[test]
+    fn motives_do_not_capture_shadowed_variables() {
+        let mut ctx = Ctx::new();
+        for (f, e) in [
+            ("f", "(ind-Nat n TODO (λ (k ih) ih))"),
+            ("g", "(match n (zero TODO) ((add1 k ih) ih))"),
+            ("h", "(rec-Nat n TODO (λ (k ih) ih))"),
+        ] {
+            eval_normalize(
+                &mut ctx,
+                &format!("(claim {} (Π ((n Nat)) (-> Nat (= Nat n n))))", f),
+            )
+            .unwrap();
+            let (result, goals) =
+                eval_with_goals(&mut ctx, &format!("(define {} (λ (n n) {}))", f, e));
+            assert_eq!(result, Ok(None));
+            assert_eq!(goals[0].goal.to_string(), "(= Nat n n)");
+        }
+    }
+
+    #[test]
+    fn eliminators_without_motives_need_a_type_to_be_checked_against() {
+        let mut ctx = Ctx::new();
+        for e in ["(ind-Nat 3 'a (λ (k ih) ih))", "(replace (same 1) 'a)"] {
+            assert_eq!(
+                eval_normalize(&mut ctx, e),
+                Err(Error::CantDetermineType(e.parse().unwrap()).to_string())
+            );
+        }
+    }
+
+    #[test]
+    fn explicit_motives_still_work() {
+        let mut ctx = Ctx::new();
+        define(
+            &mut ctx,
+            "copies",
+            "(Π ((n Nat)) (Vec Atom n))",
+            "(λ (n) (ind-Nat n (λ (k) (Vec Atom k)) vecnil (λ (k es) (vec:: 'a es))))",
+        )
+        .unwrap();
+        evaluates_to(
+            &ctx,
+            "(iter-Nat 2 (the Nat 0) (λ (k) (add1 k)))",
+            "Nat",
+            "2",
+        );
+        assert!(norm(&ctx, &"(iter-Nat 2 nil (λ (es) es))".parse().unwrap()).is_err());
+    }
+}
@@ -1,5 +1,7 @@
+use crate::alpha;
 use crate::basics::{Core, CoreInterface, Ctx, Env, Renaming, Value, ValueInterface};
 use crate::errors::{Error, Result};
+use crate::types::natural::{numeral_of_core, numeral_of_value, Numeral};
 use crate::types::values::{add1, later};
 use crate::types::{cores, values};
 use std::any::Any;
@@ -14,16 +16,42 @@ impl CoreInterface for Add1<Core> {
     impl_core_defaults!(
         (0),
         as_any,
-        same,
         occurring_names,
-        alpha_equiv,
         no_type,
         check_by_synth,
         (resugar: add1)
     );
 
+    fn same(&self, other: &dyn CoreInterface) -> bool {
+        match other.try_as::<Self>() {
+            Some(other) => self == other,
+            None => other.try_as::<Numeral>().is_some() && other.same(self),
+        }
+    }
+
+    /// The successor of a numeral is a numeral, so that arithmetic on numerals
+    /// does not build up chains of `add1`s
     fn val_of(&self, env: &Env) -> Value {
-        values::add1(later(env.clone(), self.0.clone()))
+        let n = later(env.clone(), self.0.clone());
+        match n.try_as::<Numeral>() {
+            Some(Numeral(k)) => values::numeral(k + 1u32),
+            None => values::add1(n),
+        }
+    }
+
+    fn alpha_equiv_aux(
+        &self,
+        other: &dyn CoreInterface,
+        lvl: usize,
+        b1: &alpha::Bindings,
+        b2: &alpha::Bindings,
+    ) -> bool {
+        match other.try_as::<Self>() {
+            Some(other) => self.0.alpha_equiv_aux(&other.0, lvl, b1, b2),
+            None => {
+                numeral_of_core(other).is_some() && numeral_of_core(self) == numeral_of_core(other)
+            }
+        }
     }
 
     fn synth(&self, ctx: &Ctx, r: &Renaming) -> Result<(Core, Core)> {
@@ -42,6 +70,8 @@ impl ValueInterface for Add1<Value> {
     fn same(&self, other: &dyn ValueInterface) -> bool {
         if let Some(Add1(n)) = other.as_any().downcast_ref::<Self>() {
             &self.0 == n
+        } else if other.as_any().is::<Numeral>() {
+            numeral_of_value(self) == numeral_of_value(other.as_any())
         } else {
             false
         }
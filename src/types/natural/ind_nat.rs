@@ -5,9 +5,10 @@ use crate::errors;
 use crate::normalize::{read_back, val_in_ctx};
 use crate::symbol::Symbol;
 use crate::types::functions::do_ap;
-use crate::types::natural::{Add1, Zero};
+use crate::types::natural::{Add1, Numeral, Zero};
 use crate::types::values::{add1, later};
 use crate::types::{cores, values};
+use num_bigint::BigUint;
 use std::collections::HashSet;
 use std::fmt::Formatter;
 
@@ -113,6 +114,17 @@ fn do_ind_nat(tgt_v: Value, mot_v: Value, b_v: Value, s_v: Value) -> Value {
         None => {}
     };
 
+    // count up from the base rather than peeling the numeral one add1 at a time
+    if let Some(Numeral(n)) = tgt_v.try_as::<Numeral>() {
+        let mut i = BigUint::from(0u32);
+        let mut result = b_v;
+        while &i < n {
+            result = do_ap(&do_ap(&s_v, values::numeral(i.clone())), result);
+            i += 1u32;
+        }
+        return result;
+    }
+
     match tgt_v.try_as::<Add1<Value>>() {
         Some(Add1(n_minus_1v)) => {
             return do_ap(
@@ -3,9 +3,10 @@ use crate::errors;
 use crate::normalize::{read_back, val_in_ctx};
 use crate::symbol::Symbol;
 use crate::types::functions::do_ap;
-use crate::types::natural::{Add1, Zero};
+use crate::types::natural::{Add1, Numeral, Zero};
 use crate::types::values::later;
 use crate::types::{cores, values, MaybeTyped};
+use num_bigint::BigUint;
 use std::collections::HashSet;
 
 ternary_eliminator!(IterNat, do_iter_nat, synth_iter_nat);
@@ -41,6 +42,17 @@ fn _do_iter_nat(tgt_v: &Value, bt_v: Value, b_v: Value, s_v: &Value) -> Value {
         None => {}
     };
 
+    // count up from the base rather than peeling the numeral one add1 at a time
+    if let Some(Numeral(n)) = tgt_v.try_as::<Numeral>() {
+        let mut i = BigUint::from(0u32);
+        let mut result = b_v;
+        while &i < n {
+            result = do_ap(s_v, result);
+            i += 1u32;
+        }
+        return result;
+    }
+
     match tgt_v.try_as::<Add1<Value>>() {
         Some(Add1(n_minus_1v)) => return do_ap(s_v, _do_iter_nat(n_minus_1v, bt_v, b_v, s_v)),
         None => {}
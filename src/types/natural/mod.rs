@@ -2,6 +2,7 @@ mod add1;
 mod ind_nat;
 mod iter_nat;
 mod nat;
+mod numeral;
 mod rec_nat;
 mod which_nat;
 mod zero;
@@ -10,6 +11,7 @@ pub use add1::Add1;
 pub use ind_nat::IndNat;
 pub use iter_nat::IterNat;
 pub use nat::Nat;
+pub use numeral::{is_zero, numeral_of_core, numeral_of_value, predecessor, Numeral};
 pub use rec_nat::RecNat;
 pub use which_nat::{NeutralWhichNat, WhichNat};
 pub use zero::Zero;
@@ -2,8 +2,7 @@ use crate::basics::{Core, CoreInterface, Ctx, Env, Renaming, Value, ValueInterfa
 use crate::errors;
 use crate::errors::Error;
 use crate::normalize::read_back;
-use crate::types::natural::zero::Zero;
-use crate::types::natural::Add1;
+use crate::types::natural::{numeral_of_value, Add1};
 use crate::types::{cores, values};
 use std::any::Any;
 
@@ -45,11 +44,13 @@ impl ValueInterface for Nat {
         Ok(cores::nat())
     }
 
+    /// Closed natural numbers are read back as numerals, however they were written, so that
+    /// each of them has a single normal form
     fn read_back(&self, ctx: &Ctx, tv: &Value, v: &Value) -> errors::Result<Core> {
-        if v.as_any().downcast_ref::<Zero>().is_some() {
-            Ok(cores::zero())
+        if let Some(n) = numeral_of_value(v.as_any()) {
+            Ok(cores::numeral(n))
         } else if let Some(Add1(n)) = v.as_any().downcast_ref::<Add1<Value>>() {
-            read_back(ctx, tv, n).map(cores::add1)
+            Ok(cores::add1(read_back(ctx, tv, n)?))
         } else {
             Err(Error::TypeMismatchVar(v.clone(), tv.clone()))
         }
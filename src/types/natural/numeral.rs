@@ -0,0 +1,138 @@
+use crate::alpha;
+use crate::basics::{Core, CoreInterface, Ctx, Env, Renaming, Value, ValueInterface};
+use crate::errors;
+use crate::errors::Error;
+use crate::normalize::val_in_ctx;
+use crate::symbol::Symbol;
+use crate::typechecker::same_type;
+use crate::types::integer::Int;
+use crate::types::natural::{Add1, Zero};
+use crate::types::{cores, values};
+use num_bigint::BigUint;
+use std::any::Any;
+use std::collections::HashSet;
+
+/// A natural number written in decimal rather than as a chain of `add1`s. It stands for
+/// the same number as the chain, but is peeled one `add1` at a time only when needed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Numeral(pub BigUint);
+
+/// The number that `e` stands for, if it is built from numerals, `zero`, and `add1` only
+pub fn numeral_of_core(e: &dyn CoreInterface) -> Option<BigUint> {
+    let mut e = e;
+    let mut n = BigUint::from(0u32);
+    loop {
+        if let Some(Add1(e_minus_1)) = e.try_as::<Add1<Core>>() {
+            n += 1u32;
+            e = e_minus_1;
+        } else if let Some(Numeral(k)) = e.try_as::<Numeral>() {
+            return Some(n + k);
+        } else if e.try_as::<Zero>().is_some() {
+            return Some(n);
+        } else {
+            return None;
+        }
+    }
+}
+
+/// The number that the value `v` stands for, if it is built from numerals, `zero`, and
+/// `add1` only
+pub fn numeral_of_value(v: &dyn Any) -> Option<BigUint> {
+    let mut v = v;
+    let mut n = BigUint::from(0u32);
+    loop {
+        if let Some(Add1(v_minus_1)) = v.downcast_ref::<Add1<Value>>() {
+            n += 1u32;
+            v = v_minus_1.as_any();
+        } else if let Some(Numeral(k)) = v.downcast_ref::<Numeral>() {
+            return Some(n + k);
+        } else if v.is::<Zero>() {
+            return Some(n);
+        } else {
+            return None;
+        }
+    }
+}
+
+/// Whether the natural number `n` is zero, however it is written
+pub fn is_zero(n: &Value) -> bool {
+    match n.try_as::<Numeral>() {
+        Some(Numeral(k)) => k == &BigUint::from(0u32),
+        None => n.try_as::<Zero>().is_some(),
+    }
+}
+
+/// The natural number one less than `n`, if `n` is a successor, however it is written
+pub fn predecessor(n: &Value) -> Option<Value> {
+    if let Some(Add1(n_minus_1)) = n.try_as::<Add1<Value>>() {
+        return Some(n_minus_1.clone());
+    }
+    match n.try_as::<Numeral>() {
+        Some(Numeral(k)) if k > &BigUint::from(0u32) => Some(values::numeral(k - 1u32)),
+        _ => None,
+    }
+}
+
+impl CoreInterface for Numeral {
+    impl_core_defaults!((0), as_any, no_type);
+
+    fn same(&self, other: &dyn CoreInterface) -> bool {
+        numeral_of_core(other).as_ref() == Some(&self.0)
+    }
+
+    fn occurring_names(&self) -> HashSet<Symbol> {
+        HashSet::new()
+    }
+
+    fn val_of(&self, _env: &Env) -> Value {
+        Value::new(self.clone())
+    }
+
+    fn synth(&self, _ctx: &Ctx, _r: &Renaming) -> errors::Result<(Core, Core)> {
+        Ok((cores::nat(), Core::new(self.clone())))
+    }
+
+    fn check(&self, ctx: &Ctx, r: &Renaming, tv: &Value) -> errors::Result<Core> {
+        // natural numbers are written the same way as the non-negative integers
+        if tv.try_as::<Int>().is_some() {
+            return Ok(cores::int_literal(self.0.clone().into()));
+        }
+        let (t_out, e_out) = self.synth(ctx, r)?;
+        same_type(ctx, &val_in_ctx(ctx, &t_out), tv)?;
+        Ok(e_out)
+    }
+
+    fn alpha_equiv_aux(
+        &self,
+        other: &dyn CoreInterface,
+        _lvl: usize,
+        _b1: &alpha::Bindings,
+        _b2: &alpha::Bindings,
+    ) -> bool {
+        CoreInterface::same(self, other)
+    }
+
+    fn resugar(&self) -> (HashSet<Symbol>, Core) {
+        (HashSet::new(), Core::new(self.clone()))
+    }
+}
+
+impl ValueInterface for Numeral {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn same(&self, other: &dyn ValueInterface) -> bool {
+        numeral_of_value(other.as_any()).as_ref() == Some(&self.0)
+    }
+
+    fn read_back_type(&self, _ctx: &Ctx) -> errors::Result<Core> {
+        Err(Error::NotATypeVar(Value::new(self.clone())))
+    }
+}
+
+impl std::fmt::Display for Numeral {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
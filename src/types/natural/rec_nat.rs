@@ -1,11 +1,14 @@
-use crate::basics::{Core, CoreInterface, Ctx, Env, NeutralInterface, Renaming, The, Value, N};
+use crate::basics::{
+    Closure, Core, CoreInterface, Ctx, Env, NeutralInterface, Renaming, The, Value, N,
+};
 use crate::errors;
-use crate::normalize::val_in_ctx;
+use crate::normalize::{read_back, val_in_ctx};
 use crate::symbol::Symbol;
 use crate::types::functions::do_ap;
-use crate::types::natural::{Add1, Zero};
+use crate::types::natural::{Add1, Numeral, Zero};
 use crate::types::values::later;
 use crate::types::{cores, values, MaybeTyped};
+use num_bigint::BigUint;
 use std::collections::HashSet;
 
 ternary_eliminator!(RecNat, do_rec_nat, synth_rec_nat);
@@ -44,6 +47,17 @@ fn _do_rec_nat(tgt_v: &Value, bt_v: &Value, b_v: &Value, s_v: &Value) -> Value {
         None => {}
     };
 
+    // count up from the base rather than peeling the numeral one add1 at a time
+    if let Some(Numeral(n)) = tgt_v.try_as::<Numeral>() {
+        let mut i = BigUint::from(0u32);
+        let mut result = b_v.clone();
+        while &i < n {
+            result = do_ap(&do_ap(s_v, values::numeral(i.clone())), result);
+            i += 1u32;
+        }
+        return result;
+    }
+
     match tgt_v.try_as::<Add1<Value>>() {
         Some(Add1(n_minus_1v)) => {
             return do_ap(
@@ -54,12 +68,38 @@ fn _do_rec_nat(tgt_v: &Value, bt_v: &Value, b_v: &Value, s_v: &Value) -> Value {
         None => {}
     };
 
+    if let Some((_, ne)) = tgt_v.as_neutral() {
+        let bt = bt_v.clone();
+        let step_type = values::pi(
+            "n-1",
+            values::nat(),
+            Closure::higher(move |_n_minus_one| {
+                let bt = bt.clone();
+                values::pi("old", bt.clone(), Closure::higher(move |_old| bt.clone()))
+            }),
+        );
+        return values::neutral(
+            bt_v.clone(),
+            NeutralRecNat(
+                ne.clone(),
+                The(bt_v.clone(), b_v.clone()),
+                The(step_type, s_v.clone()),
+            ),
+        );
+    }
+
     unreachable!("{:?}", tgt_v)
 }
 
 impl NeutralInterface for NeutralRecNat {
-    fn read_back_neutral(&self, _ctx: &Ctx) -> errors::Result<Core> {
-        todo!()
+    fn read_back_neutral(&self, ctx: &Ctx) -> errors::Result<Core> {
+        let NeutralRecNat(tgt, The(b_tv, b_v), The(s_tv, s_v)) = self;
+        Ok(cores::rec_nat_desugared(
+            tgt.read_back_neutral(ctx)?,
+            b_tv.read_back_type(ctx)?,
+            read_back(ctx, b_tv, b_v)?,
+            read_back(ctx, s_tv, s_v)?,
+        ))
     }
 }
 
@@ -5,7 +5,7 @@ use crate::errors;
 use crate::normalize::read_back;
 use crate::symbol::Symbol;
 use crate::types::functions::do_ap;
-use crate::types::natural::{Add1, Zero};
+use crate::types::natural::{is_zero, predecessor};
 use crate::types::values::later;
 use crate::types::{cores, values, MaybeTyped};
 use std::collections::HashSet;
@@ -44,15 +44,13 @@ fn synth_which_nat(
 }
 
 fn do_which_nat(tgt_v: Value, bt_v: Value, b_v: Value, s_v: Value) -> Value {
-    match tgt_v.try_as::<Zero>() {
-        Some(_) => return b_v,
-        None => {}
-    };
+    if is_zero(&tgt_v) {
+        return b_v;
+    }
 
-    match tgt_v.try_as::<Add1<Value>>() {
-        Some(Add1(n_minus_1v)) => return do_ap(&s_v, n_minus_1v.clone()),
-        None => {}
-    };
+    if let Some(n_minus_1v) = predecessor(&tgt_v) {
+        return do_ap(&s_v, n_minus_1v);
+    }
 
     match tgt_v.as_neutral() {
         Some((_, ne)) => {
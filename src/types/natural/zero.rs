@@ -1,6 +1,7 @@
 use crate::basics::{Core, CoreInterface, Ctx, Env, Renaming, Value, ValueInterface};
 use crate::errors;
 use crate::errors::Error;
+use crate::types::natural::{numeral_of_core, numeral_of_value};
 use crate::types::values::zero;
 use crate::types::{cores, values};
 use std::any::Any;
@@ -13,7 +14,6 @@ impl CoreInterface for Zero {
     impl_core_defaults!(
         _,
         as_any,
-        same,
         occurring_names,
         alpha_equiv,
         no_type,
@@ -21,6 +21,10 @@ impl CoreInterface for Zero {
         (resugar: zero)
     );
 
+    fn same(&self, other: &dyn CoreInterface) -> bool {
+        numeral_of_core(other) == Some(0u32.into())
+    }
+
     fn val_of(&self, _env: &Env) -> Value {
         values::zero()
     }
@@ -36,7 +40,7 @@ impl ValueInterface for Zero {
     }
 
     fn same(&self, other: &dyn ValueInterface) -> bool {
-        other.as_any().is::<Self>()
+        numeral_of_value(other.as_any()) == Some(0u32.into())
     }
 
     fn read_back_type(&self, _ctx: &Ctx) -> errors::Result<Core> {
@@ -6,10 +6,12 @@ use crate::basics::{
 use crate::errors::{Error, Result};
 use crate::normalize::{read_back, val_in_ctx};
 use crate::symbol::Symbol;
+use crate::types::record::RecordType;
 use crate::types::reference::NeutralVar;
 use crate::types::values::later;
 use crate::types::{
-    check_with_fresh_binding, cores, is_type_with_fresh_binding, occurring_binder_names, values,
+    check_with_fresh_binding, cores, is_type_with_fresh_binding, occurring_binder_names,
+    synth_type_level, values,
 };
 use std::any::Any;
 use std::collections::HashSet;
@@ -51,7 +53,7 @@ impl CoreInterface for Sigma<Core, Core> {
     impl_core_defaults!((arg_name, car_type, cdr_type), as_any, same, check_by_synth);
 
     fn occurring_names(&self) -> HashSet<Symbol> {
-        todo!()
+        &occurring_binder_names(&self.arg_name, &self.car_type) | &self.cdr_type.occurring_names()
     }
 
     fn val_of(&self, env: &Env) -> Value {
@@ -74,9 +76,12 @@ impl CoreInterface for Sigma<Core, Core> {
     }
 
     fn synth(&self, ctx: &Ctx, r: &Renaming) -> Result<(Core, Core)> {
-        let (x_hat, a_out, d_out) =
+        let (x_hat, a_out, d_out, level) =
             check_with_fresh_binding(ctx, r, &self.arg_name, &self.car_type, &self.cdr_type)?;
-        Ok((cores::universe(), cores::sigma(x_hat, a_out, d_out)))
+        Ok((
+            cores::universe_level(level),
+            cores::sigma(x_hat, a_out, d_out),
+        ))
     }
 
     fn alpha_equiv_aux(
@@ -140,7 +145,7 @@ impl CoreInterface for SigmaStar {
                     cdr_type: self.cdr_type.clone(),
                 };
 
-                let (z, a_out, d_out) = check_with_fresh_binding(ctx, r, x, a, &body)?;
+                let (z, a_out, d_out, _) = check_with_fresh_binding(ctx, r, x, a, &body)?;
 
                 Ok(cores::sigma(z, a_out, d_out))
             }
@@ -162,9 +167,12 @@ impl CoreInterface for SigmaStar {
                     cdr_type: self.cdr_type.clone(),
                 };
 
-                let (x_hat, a_out, d_out) = check_with_fresh_binding(ctx, r, x, a, &body)?;
+                let (x_hat, a_out, d_out, level) = check_with_fresh_binding(ctx, r, x, a, &body)?;
 
-                Ok((cores::universe(), cores::sigma(x_hat, a_out, d_out)))
+                Ok((
+                    cores::universe_level(level),
+                    cores::sigma(x_hat, a_out, d_out),
+                ))
             }
         }
     }
@@ -199,13 +207,16 @@ impl CoreInterface for Pair<Core> {
 
     fn synth(&self, ctx: &Ctx, r: &Renaming) -> Result<(Core, Core)> {
         let a = ctx.fresh(&Symbol::new("a"));
-        let a_out = self.0.check(ctx, r, &values::universe())?;
-        let d_out = self.1.check(
+        let (a_out, a_level) = synth_type_level(ctx, r, &self.0)?;
+        let (d_out, d_level) = synth_type_level(
             &ctx.bind_free(a.clone(), val_in_ctx(ctx, &a_out))?,
             r,
-            &values::universe(),
+            &self.1,
         )?;
-        Ok((cores::universe(), cores::sigma(a, a_out, d_out)))
+        Ok((
+            cores::universe_level(a_level.max(d_level)),
+            cores::sigma(a, a_out, d_out),
+        ))
     }
 
     fn resugar(&self) -> (HashSet<Symbol>, Core) {
@@ -231,12 +242,16 @@ impl CoreInterface for Cons<Core> {
         )
     }
 
-    fn synth(&self, _ctx: &Ctx, _r: &Renaming) -> Result<(Core, Core)> {
-        Err(Error::CantDetermineType(Core::new(self.clone())))
+    fn synth(&self, ctx: &Ctx, r: &Renaming) -> Result<(Core, Core)> {
+        // a pair of synthesizable parts has a non-dependent type, like `(Pair A D)`
+        let (a_t_out, a_out) = self.0.synth(ctx, r)?;
+        let (d_t_out, d_out) = self.1.synth(ctx, r)?;
+        let a = ctx.fresh(&Symbol::new("a"));
+        Ok((cores::sigma(a, a_t_out, d_t_out), cores::cons(a_out, d_out)))
     }
 
     fn check(&self, ctx: &Ctx, r: &Renaming, tv: &Value) -> Result<Core> {
-        if let Some(sigma) = tv.try_as::<Sigma<Value, Closure>>() {
+        if let Some(sigma) = as_sigma(tv) {
             let a_out = self.0.check(ctx, r, &sigma.car_type)?;
             let d_out = self
                 .1
@@ -270,7 +285,7 @@ impl CoreInterface for Car<Core> {
         match self.0.synth(ctx, r)? {
             (p_t, p_out) => {
                 let val = val_in_ctx(ctx, &p_t);
-                match val.try_as::<Sigma<Value, Closure>>() {
+                match as_sigma(&val) {
                     Some(Sigma { car_type: a, .. }) => {
                         Ok((a.read_back_type(ctx)?, cores::car(p_out)))
                     }
@@ -307,7 +322,7 @@ impl CoreInterface for Cdr<Core> {
         match self.0.synth(ctx, r)? {
             (p_t, p_out) => {
                 let val = val_in_ctx(ctx, &p_t);
-                match val.try_as::<Sigma<Value, Closure>>() {
+                match as_sigma(&val) {
                     Some(Sigma { cdr_type: c, .. }) => Ok((
                         c.val_of(do_car(&val_in_ctx(ctx, &p_out)))
                             .read_back_type(ctx)?,
@@ -414,36 +429,55 @@ impl_sexpr_display!(T: Cons<T>, ("cons", 0, 1));
 impl_sexpr_display!(T: Car<T>, ("car", 0));
 impl_sexpr_display!(T: Cdr<T>, ("cdr", 0));
 
-fn do_car(pv: &Value) -> Value {
+/// The Σ type `tv`, seeing through record types to the Σ they are laid out as
+pub fn as_sigma(tv: &Value) -> Option<&Sigma<Value, Closure>> {
+    match tv.try_as::<RecordType>() {
+        Some(rec) => rec.layout.try_as(),
+        None => tv.try_as(),
+    }
+}
+
+pub fn do_car(pv: &Value) -> Value {
     match pv.try_as::<Cons<Value>>() {
         Some(Cons(a, _)) => return a.clone(),
         None => {}
     }
 
     match pv.as_neutral() {
-        Some((p, ne)) => match p.try_as::<Sigma<Value, Closure>>() {
-            Some(s) => return values::neutral(s.car_type.clone(), NeutralCar(ne.clone())),
-            None => {}
-        },
+        Some((p, ne)) => {
+            // the fields of neutral records remember their names
+            if let Some(v) = p.try_as::<RecordType>().and_then(|rec| rec.neutral_car(ne)) {
+                return v;
+            }
+            match as_sigma(p) {
+                Some(s) => return values::neutral(s.car_type.clone(), NeutralCar(ne.clone())),
+                None => {}
+            }
+        }
         None => {}
     }
 
     unreachable!("{:?}", pv)
 }
 
-fn do_cdr(pv: &Value) -> Value {
+pub fn do_cdr(pv: &Value) -> Value {
     match pv.try_as::<Cons<Value>>() {
         Some(Cons(_, d)) => return d.clone(),
         None => {}
     }
 
     match pv.as_neutral() {
-        Some((p, ne)) => match p.try_as::<Sigma<Value, Closure>>() {
-            Some(s) => {
-                return values::neutral(s.cdr_type.val_of(do_car(pv)), NeutralCdr(ne.clone()))
+        Some((p, ne)) => {
+            if let Some(v) = p.try_as::<RecordType>().and_then(|rec| rec.neutral_cdr(ne)) {
+                return v;
             }
-            None => {}
-        },
+            match as_sigma(p) {
+                Some(s) => {
+                    return values::neutral(s.cdr_type.val_of(do_car(pv)), NeutralCdr(ne.clone()))
+                }
+                None => {}
+            }
+        }
         None => {}
     }
 
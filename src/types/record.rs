@@ -0,0 +1,528 @@
+use crate::alpha;
+use crate::alpha::alpha_equiv_aux;
+use crate::basics::{
+    is_var_name, Closure, Core, CoreInterface, Ctx, Env, NeutralInterface, Renaming, The, Value,
+    ValueInterface, N, R,
+};
+use crate::errors::{Error, Result};
+use crate::normalize::{read_back, val_in_ctx};
+use crate::symbol::Symbol;
+use crate::types::pairs::{do_car, do_cdr, Cdr, NeutralCdr, Sigma};
+use crate::types::{cores, values};
+use maplit::hashset;
+use std::any::Any;
+use std::collections::HashSet;
+use std::fmt::Formatter;
+
+/// A record type with named fields, as introduced by a top-level `(record ...)` form.
+///
+/// The fields are laid out as a Σ type whose binders are the field names, so the type of a
+/// field may refer to the fields before it. A record with a single field is just that
+/// field, and a record without fields is `Trivial`. Values are built with `cons` and taken
+/// apart with `car` and `cdr` like those of any Σ type; the field names only matter when
+/// elaborating `make` and `field` and when printing.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordDecl {
+    pub name: Symbol,
+    pub fields: Vec<(Symbol, Core)>,
+}
+
+/// A declared record type, together with the Σ type it is laid out as.
+///
+/// Taking the `cdr` of a neutral record leaves the rest of its fields, whose type is a record
+/// type that starts `from` a later field. It reads back as its layout, but keeps the field
+/// names for the neutral fields taken from it.
+#[derive(Debug, Clone)]
+pub struct RecordType {
+    decl: R<RecordDecl>,
+    from: usize,
+    pub layout: Value,
+}
+
+/// A record type as read back: its name, standing for the Σ type it is laid out as
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordName {
+    decl: R<RecordDecl>,
+    layout: Core,
+}
+
+/// `(make R (field expr) ...)`: a value of the record type `R`, with every field given once
+#[derive(Debug, Clone, PartialEq)]
+pub struct Make {
+    record: Symbol,
+    fields: Vec<(Symbol, Core)>,
+}
+
+/// `(field e x)`: the field `x` of the record `e`, which elaborates to `car`s and `cdr`s
+#[derive(Debug, Clone, PartialEq)]
+pub struct Field(pub Core, pub Symbol);
+
+/// A field of a neutral record as read back: the `car`s and `cdr`s that project it out of
+/// `target`, printed as `(field target x)`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Projection {
+    target: Core,
+    field: Symbol,
+    chain: Core,
+}
+
+/// The field `field` of a neutral record, projected out of `rest`, the rest of the record
+/// after `depth` fields. The last field is the `cdr` of the rest, and any other the `car`.
+#[derive(Debug)]
+pub struct NeutralField {
+    rest: N,
+    depth: usize,
+    field: Symbol,
+    last: bool,
+}
+
+impl RecordDecl {
+    fn field_names(&self) -> impl Iterator<Item = &Symbol> {
+        self.fields.iter().map(|(x, _)| x)
+    }
+}
+
+impl RecordType {
+    /// The `car` of a neutral record with at least two fields left, which is the field `from`
+    pub fn neutral_car(&self, ne: &N) -> Option<Value> {
+        let sigma = self.sigma()?;
+        Some(values::neutral(
+            sigma.car_type.clone(),
+            NeutralField {
+                rest: ne.clone(),
+                depth: self.from,
+                field: self.decl.fields[self.from].0.clone(),
+                last: false,
+            },
+        ))
+    }
+
+    /// The `cdr` of a neutral record with at least two fields left: the last field, or the
+    /// rest of the record
+    pub fn neutral_cdr(&self, ne: &N) -> Option<Value> {
+        let sigma = self.sigma()?;
+        let tv = sigma.cdr_type.val_of(self.neutral_car(ne)?);
+        let next = self.from + 1;
+        if next + 1 == self.decl.fields.len() {
+            Some(values::neutral(
+                tv,
+                NeutralField {
+                    rest: ne.clone(),
+                    depth: self.from,
+                    field: self.decl.fields[next].0.clone(),
+                    last: true,
+                },
+            ))
+        } else {
+            let rest_tv = Value::new(RecordType {
+                decl: self.decl.clone(),
+                from: next,
+                layout: tv,
+            });
+            Some(values::neutral(rest_tv, NeutralCdr(ne.clone())))
+        }
+    }
+
+    /// The layout as a Σ type, unless fewer than two fields are left
+    fn sigma(&self) -> Option<&Sigma<Value, Closure>> {
+        if self.from + 2 > self.decl.fields.len() {
+            return None;
+        }
+        self.layout.try_as()
+    }
+}
+
+impl Make {
+    pub fn new(record: Symbol, fields: Vec<(Symbol, Core)>) -> Self {
+        Make { record, fields }
+    }
+
+    /// The pairs of the fields, which are in the order of the declaration once elaborated
+    fn unfold(&self) -> Core {
+        tuple(self.fields.iter().map(|(_, e)| e.clone()).collect())
+    }
+}
+
+impl Projection {
+    pub fn new(target: Core, field: Symbol, chain: Core) -> Self {
+        Projection {
+            target,
+            field,
+            chain,
+        }
+    }
+}
+
+/// The pairs, Σ type or `car`/`cdr` chain that `e` stands for, if it was read back from a
+/// record. Alpha-equivalence compares them instead, so records are interchangeable with their
+/// layouts.
+pub fn unfold(e: &dyn CoreInterface) -> Option<Core> {
+    if let Some(t) = e.try_as::<RecordName>() {
+        Some(t.layout.clone())
+    } else if let Some(m) = e.try_as::<Make>() {
+        Some(m.unfold())
+    } else {
+        e.try_as::<Projection>().map(|p| p.chain.clone())
+    }
+}
+
+/// Check a record declaration and add its type to the context
+pub fn declare(ctx: &Ctx, decl: RecordDecl) -> Result<Ctx> {
+    if !is_var_name(&decl.name) {
+        return Err(Error::NotAVarName(decl.name.clone()));
+    }
+    for (i, (x, _)) in decl.fields.iter().enumerate() {
+        if decl.fields[..i].iter().any(|(y, _)| y == x) {
+            return Err(Error::DuplicateField(x.clone()));
+        }
+    }
+
+    let layout_out = layout(&decl.fields).check(ctx, &Renaming::new(), &values::universe())?;
+    let layout = val_in_ctx(ctx, &layout_out);
+    let decl = R::new(decl);
+    ctx.define_primitive(decl.name.clone(), cores::universe(), move |_| {
+        Value::new(RecordType {
+            decl: decl.clone(),
+            from: 0,
+            layout: layout.clone(),
+        })
+    })
+}
+
+fn layout(fields: &[(Symbol, Core)]) -> Core {
+    match fields {
+        [] => cores::trivial(),
+        [(_, t)] => t.clone(),
+        [init @ .., (_, t)] => cores::sigma_star(init.to_vec(), t.clone()),
+    }
+}
+
+/// Nest the fields' expressions into pairs, matching the record's layout
+fn tuple(mut es: Vec<Core>) -> Core {
+    let last = es.pop().unwrap_or_else(cores::sole);
+    es.into_iter().rev().fold(last, |d, a| cores::cons(a, d))
+}
+
+/// The `car`s and `cdr`s that project the field `i` out of `e`, a record with `n` fields
+fn chain(e: Core, i: usize, n: usize) -> Core {
+    let rest = (0..i).fold(e, |e, _| cores::cdr(e));
+    if i + 1 < n {
+        cores::car(rest)
+    } else {
+        rest
+    }
+}
+
+/// The fields of the record `v`, whose type is `rec`, together with their types
+fn components(rec: &RecordType, v: Value) -> Vec<The> {
+    let n = rec.decl.fields.len();
+    let mut tv = rec.layout.clone();
+    let mut rest = v;
+    let mut fields = vec![];
+    for _ in 1..n {
+        let next_tv = match tv.try_as::<Sigma<Value, Closure>>() {
+            Some(sigma) => {
+                let e = do_car(&rest);
+                fields.push(The(sigma.car_type.clone(), e.clone()));
+                sigma.cdr_type.val_of(e)
+            }
+            None => unreachable!("{:?}", tv),
+        };
+        tv = next_tv;
+        rest = do_cdr(&rest);
+    }
+    if n > 0 {
+        fields.push(The(tv, rest));
+    }
+    fields
+}
+
+impl CoreInterface for Make {
+    impl_core_defaults!((record, fields), as_any, same, no_type, check_by_synth);
+
+    fn occurring_names(&self) -> HashSet<Symbol> {
+        let mut names = HashSet::new();
+        names.insert(self.record.clone());
+        for (_, e) in &self.fields {
+            names.extend(e.occurring_names());
+        }
+        names
+    }
+
+    fn val_of(&self, env: &Env) -> Value {
+        self.unfold().val_of(env)
+    }
+
+    fn synth(&self, ctx: &Ctx, r: &Renaming) -> Result<(Core, Core)> {
+        let t_out = cores::refer(self.record.clone()).is_type(ctx, r)?;
+        let tv = val_in_ctx(ctx, &t_out);
+        let rec = match tv.try_as::<RecordType>() {
+            Some(rec) => rec,
+            None => return Err(Error::NotARecordType(t_out)),
+        };
+
+        for (i, (x, _)) in self.fields.iter().enumerate() {
+            if !rec.decl.field_names().any(|y| y == x) {
+                return Err(Error::NotAFieldOf(x.clone(), t_out));
+            }
+            if self.fields[..i].iter().any(|(y, _)| y == x) {
+                return Err(Error::DuplicateField(x.clone()));
+            }
+        }
+
+        let n = rec.decl.fields.len();
+        let mut field_tv = rec.layout.clone();
+        let mut fields_out = vec![];
+        for (i, x) in rec.decl.field_names().enumerate() {
+            let e = match self.fields.iter().find(|(y, _)| y == x) {
+                Some((_, e)) => e,
+                None => return Err(Error::MissingField(x.clone())),
+            };
+            if i + 1 == n {
+                fields_out.push(e.check(ctx, r, &field_tv)?);
+            } else {
+                let next_tv = match field_tv.try_as::<Sigma<Value, Closure>>() {
+                    Some(sigma) => {
+                        let e_out = e.check(ctx, r, &sigma.car_type)?;
+                        let next_tv = sigma.cdr_type.val_of(val_in_ctx(ctx, &e_out));
+                        fields_out.push(e_out);
+                        next_tv
+                    }
+                    None => unreachable!("{:?}", field_tv),
+                };
+                field_tv = next_tv;
+            }
+        }
+
+        Ok((t_out, tuple(fields_out)))
+    }
+
+    fn alpha_equiv_aux(
+        &self,
+        other: &dyn CoreInterface,
+        lvl: usize,
+        b1: &alpha::Bindings,
+        b2: &alpha::Bindings,
+    ) -> bool {
+        self.unfold().alpha_equiv_aux(other, lvl, b1, b2)
+    }
+
+    fn resugar(&self) -> (HashSet<Symbol>, Core) {
+        let mut names = HashSet::new();
+        let mut fields = vec![];
+        for (x, e) in &self.fields {
+            let (e_names, e) = e.resugar();
+            names.extend(e_names);
+            fields.push((x.clone(), e));
+        }
+        (names, cores::make(self.record.clone(), fields))
+    }
+}
+
+impl CoreInterface for Field {
+    impl_core_defaults!((0, 1), as_any, same, no_type, check_by_synth);
+
+    fn occurring_names(&self) -> HashSet<Symbol> {
+        self.0.occurring_names()
+    }
+
+    fn val_of(&self, _env: &Env) -> Value {
+        unimplemented!("evaluate a desugared field projection instead")
+    }
+
+    fn synth(&self, ctx: &Ctx, r: &Renaming) -> Result<(Core, Core)> {
+        let (e_t, e_out) = self.0.synth(ctx, r)?;
+        let tv = val_in_ctx(ctx, &e_t);
+        let rec = match tv.try_as::<RecordType>() {
+            Some(rec) => rec,
+            None => return Err(Error::NotARecordType(e_t)),
+        };
+        let i = match rec.decl.field_names().position(|x| x == &self.1) {
+            Some(i) => i,
+            None => return Err(Error::NotAFieldOf(self.1.clone(), e_t)),
+        };
+
+        let n = rec.decl.fields.len();
+        let The(field_tv, _) = &components(rec, val_in_ctx(ctx, &e_out))[i];
+        Ok((field_tv.read_back_type(ctx)?, chain(e_out, i, n)))
+    }
+
+    fn alpha_equiv_aux(
+        &self,
+        other: &dyn CoreInterface,
+        lvl: usize,
+        b1: &alpha::Bindings,
+        b2: &alpha::Bindings,
+    ) -> bool {
+        match other.try_as::<Self>() {
+            Some(other) => self.1 == other.1 && alpha_equiv_aux(lvl, b1, b2, &self.0, &other.0),
+            None => false,
+        }
+    }
+
+    fn resugar(&self) -> (HashSet<Symbol>, Core) {
+        let (names, e) = self.0.resugar();
+        (names, cores::field(e, self.1.clone()))
+    }
+}
+
+impl CoreInterface for Projection {
+    impl_core_defaults!(
+        (target, field, chain),
+        as_any,
+        same,
+        no_type,
+        check_by_synth
+    );
+
+    fn occurring_names(&self) -> HashSet<Symbol> {
+        self.chain.occurring_names()
+    }
+
+    fn val_of(&self, env: &Env) -> Value {
+        self.chain.val_of(env)
+    }
+
+    fn synth(&self, ctx: &Ctx, r: &Renaming) -> Result<(Core, Core)> {
+        self.chain.synth(ctx, r)
+    }
+
+    fn alpha_equiv_aux(
+        &self,
+        other: &dyn CoreInterface,
+        lvl: usize,
+        b1: &alpha::Bindings,
+        b2: &alpha::Bindings,
+    ) -> bool {
+        self.chain.alpha_equiv_aux(other, lvl, b1, b2)
+    }
+
+    fn resugar(&self) -> (HashSet<Symbol>, Core) {
+        let (names, e) = self.target.resugar();
+        (names, cores::field(e, self.field.clone()))
+    }
+}
+
+impl CoreInterface for RecordName {
+    impl_core_defaults!((decl, layout), as_any, same, simple_type, check_by_synth);
+
+    fn occurring_names(&self) -> HashSet<Symbol> {
+        let mut names = self.layout.occurring_names();
+        names.insert(self.decl.name.clone());
+        names
+    }
+
+    fn val_of(&self, env: &Env) -> Value {
+        Value::new(RecordType {
+            decl: self.decl.clone(),
+            from: 0,
+            layout: self.layout.val_of(env),
+        })
+    }
+
+    fn synth(&self, _ctx: &Ctx, _r: &Renaming) -> Result<(Core, Core)> {
+        Ok((cores::universe(), Core::new(self.clone())))
+    }
+
+    fn alpha_equiv_aux(
+        &self,
+        other: &dyn CoreInterface,
+        lvl: usize,
+        b1: &alpha::Bindings,
+        b2: &alpha::Bindings,
+    ) -> bool {
+        self.layout.alpha_equiv_aux(other, lvl, b1, b2)
+    }
+
+    fn resugar(&self) -> (HashSet<Symbol>, Core) {
+        let name = self.decl.name.clone();
+        (hashset![name.clone()], cores::refer(name))
+    }
+}
+
+impl std::fmt::Display for Make {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "(make {}", self.record.name())?;
+        for (x, e) in &self.fields {
+            write!(f, " ({} {})", x.name(), e)?;
+        }
+        write!(f, ")")
+    }
+}
+
+impl std::fmt::Display for Field {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "(field {} {})", self.0, self.1.name())
+    }
+}
+
+impl std::fmt::Display for Projection {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "(field {} {})", self.target, self.field.name())
+    }
+}
+
+impl std::fmt::Display for RecordName {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.decl.name.name())
+    }
+}
+
+impl ValueInterface for RecordType {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn same(&self, other: &dyn ValueInterface) -> bool {
+        if let Some(other) = other.try_as::<Self>() {
+            self.decl.name == other.decl.name && self.from == other.from
+        } else {
+            false
+        }
+    }
+
+    fn read_back_type(&self, ctx: &Ctx) -> Result<Core> {
+        let layout = self.layout.read_back_type(ctx)?;
+        if self.from > 0 {
+            return Ok(layout);
+        }
+        Ok(Core::new(RecordName {
+            decl: self.decl.clone(),
+            layout,
+        }))
+    }
+
+    /// Records are read back field by field, so they are printed with their field names
+    fn read_back(&self, ctx: &Ctx, _tv: &Value, v: &Value) -> Result<Core> {
+        if self.from > 0 {
+            return read_back(ctx, &self.layout, v);
+        }
+        let fields = self
+            .decl
+            .field_names()
+            .zip(components(self, v.clone()))
+            .map(|(x, The(t, e))| Ok((x.clone(), read_back(ctx, &t, &e)?)))
+            .collect::<Result<_>>()?;
+        Ok(cores::make(self.decl.name.clone(), fields))
+    }
+}
+
+impl NeutralInterface for NeutralField {
+    fn read_back_neutral(&self, ctx: &Ctx) -> Result<Core> {
+        let rest = self.rest.read_back_neutral(ctx)?;
+        // the rest of the record is the record itself after `depth` cdrs
+        let mut target = rest.clone();
+        for _ in 0..self.depth {
+            target = match target.try_as::<Cdr<Core>>() {
+                Some(Cdr(e)) => e.clone(),
+                None => unreachable!("{}", target),
+            };
+        }
+        let chain = if self.last {
+            cores::cdr(rest)
+        } else {
+            cores::car(rest)
+        };
+        Ok(cores::projection(target, self.field.clone(), chain))
+    }
+}
@@ -1,10 +1,13 @@
 use crate::alpha;
 use crate::basics::{
-    is_var_name, Core, CoreInterface, Ctx, Env, NeutralInterface, Renaming, Value,
+    is_var_name, Closure, Core, CoreInterface, Ctx, Env, NeutralInterface, Renaming, Value,
 };
 use crate::errors::{Error, Result};
+use crate::normalize::val_in_ctx;
 use crate::symbol::Symbol;
-use crate::types::{cores, values};
+use crate::typechecker::same_type;
+use crate::types::functions::ImplicitPi;
+use crate::types::{cores, synth_type_level};
 use maplit::hashset;
 use std::collections::HashSet;
 use std::fmt::Formatter;
@@ -22,10 +25,14 @@ impl Ref {
         assert!(is_var_name(&s));
         Ref(s)
     }
+
+    pub fn name(&self) -> &Symbol {
+        &self.0
+    }
 }
 
 impl CoreInterface for Ref {
-    impl_core_defaults!((0), as_any, same, check_by_synth);
+    impl_core_defaults!((0), as_any, same);
 
     fn occurring_names(&self) -> HashSet<Symbol> {
         hashset![self.0.clone()]
@@ -36,8 +43,8 @@ impl CoreInterface for Ref {
     }
 
     fn is_type(&self, ctx: &Ctx, r: &Renaming) -> Result<Core> {
-        match self.check(ctx, r, &values::universe()) {
-            Ok(t_out) => Ok(t_out),
+        match synth_type_level(ctx, r, self) {
+            Ok((t_out, _)) => Ok(t_out),
             Err(_) => ctx.var_type(&self.0).and_then(|other_tv| {
                 Err(Error::WrongType(
                     other_tv.read_back_type(ctx)?,
@@ -53,6 +60,21 @@ impl CoreInterface for Ref {
         Ok((xtv.read_back_type(ctx)?, cores::refer(real_x)))
     }
 
+    fn check(&self, ctx: &Ctx, r: &Renaming, tv: &Value) -> Result<Core> {
+        let real_x = r.rename(&self.0);
+        if ctx
+            .var_type(&real_x)?
+            .try_as::<ImplicitPi<Value, Closure>>()
+            .is_some()
+        {
+            // a variable is an application without explicit arguments
+            return cores::app_star(Core::new(self.clone()), vec![]).check(ctx, r, tv);
+        }
+        let (t_out, e_out) = self.synth(ctx, r)?;
+        same_type(ctx, &val_in_ctx(ctx, &t_out), tv)?;
+        Ok(e_out)
+    }
+
     fn alpha_equiv_aux(
         &self,
         other: &dyn CoreInterface,
@@ -0,0 +1,231 @@
+use crate::basics::{Closure, Core, CoreInterface, Ctx, Env, Renaming, Value};
+use crate::errors::{Error, Result};
+use crate::normalize::{read_back, val_in_ctx};
+use crate::symbol::Symbol;
+use crate::typechecker::{convert, same_type};
+use crate::types::atom::Atom;
+use crate::types::either::Either;
+use crate::types::equality::Equal;
+use crate::types::functions::Pi;
+use crate::types::lists::List;
+use crate::types::natural::{is_zero, predecessor, Nat};
+use crate::types::pairs::Sigma;
+use crate::types::reference::NeutralVar;
+use crate::types::trivial::Trivial;
+use crate::types::vec::Vector;
+use crate::types::{cores, values};
+use std::collections::HashSet;
+use std::fmt::Formatter;
+
+/// A term of the expected type that is found by proof search: it is built from introduction
+/// forms, such as `sole`, `same` and `cons`, and from the names in scope, applied to
+/// arguments that are found in the same way. Terms are nested no deeper than `depth`, and
+/// shallower ones are preferred.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Search {
+    depth: usize,
+}
+
+impl Search {
+    pub fn new(depth: usize) -> Self {
+        Search { depth }
+    }
+}
+
+impl CoreInterface for Search {
+    impl_core_defaults!((depth), as_any, same, no_type, no_synth, no_alpha_equiv);
+
+    fn occurring_names(&self) -> HashSet<Symbol> {
+        HashSet::new()
+    }
+
+    fn val_of(&self, _env: &Env) -> Value {
+        unimplemented!("evaluate the term that the search found instead")
+    }
+
+    fn check(&self, ctx: &Ctx, _r: &Renaming, tv: &Value) -> Result<Core> {
+        // the proof refers to the names in ctx, so there is nothing left to rename
+        search(ctx, tv, self.depth)?.check(ctx, &Renaming::new(), tv)
+    }
+
+    fn resugar(&self) -> (HashSet<Symbol>, Core) {
+        unimplemented!("resugar the term that the search found instead")
+    }
+}
+
+impl std::fmt::Display for Search {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "(search {})", self.depth)
+    }
+}
+
+/// Find a term of type `tv`, trying each depth up to `depth` in turn
+fn search(ctx: &Ctx, tv: &Value, depth: usize) -> Result<Core> {
+    for d in 0..=depth {
+        if let Some(proof) = proofs(ctx, tv, d)?.into_iter().next() {
+            return Ok(proof);
+        }
+    }
+    Err(Error::NoProofFound(tv.read_back_type(ctx)?))
+}
+
+/// All the terms of type `tv` that are nested no deeper than `depth`
+fn proofs(ctx: &Ctx, tv: &Value, depth: usize) -> Result<Vec<Core>> {
+    // any function can be written as a λ, so there is no need to look further
+    if let Some(pi) = tv.try_as::<Pi<Value, Closure>>() {
+        let x = ctx.fresh(&pi.arg_name);
+        let ctx_hat = ctx.bind_free(x.clone(), pi.arg_type.clone())?;
+        let x_val = values::neutral(pi.arg_type.clone(), NeutralVar(x.clone()));
+        return Ok(proofs(&ctx_hat, &pi.res_type.val_of(x_val), depth)?
+            .into_iter()
+            .map(|body| cores::lambda(x.clone(), body))
+            .collect());
+    }
+
+    let mut found = vec![];
+    for (x, xt) in ctx.typed_names() {
+        applications(ctx, cores::refer(x), &xt, tv, depth, &mut found)?;
+    }
+    found.extend(introductions(ctx, tv, depth)?);
+    Ok(found)
+}
+
+/// Add `e`, whose type is `et`, to `found` if it has the type `goal`, and likewise its
+/// applications to arguments nested no deeper than `depth - 1`
+fn applications(
+    ctx: &Ctx,
+    e: Core,
+    et: &Value,
+    goal: &Value,
+    depth: usize,
+    found: &mut Vec<Core>,
+) -> Result<()> {
+    if same_type(ctx, et, goal).is_ok() {
+        found.push(e.clone());
+    }
+
+    let pi = match et.try_as::<Pi<Value, Closure>>() {
+        Some(pi) if depth > 0 && may_return(ctx, et, goal)? => pi,
+        _ => return Ok(()),
+    };
+    // when the result's type does not depend on the argument, any argument will do
+    let args = if depends_on_arg(ctx, &pi.arg_name, &pi.arg_type, &pi.res_type)? {
+        proofs(ctx, &pi.arg_type, depth - 1)?
+    } else {
+        first_proof(ctx, &pi.arg_type, depth - 1)?
+            .into_iter()
+            .collect()
+    };
+    for arg in args {
+        let arg_val = val_in_ctx(ctx, &arg);
+        let app = cores::app(e.clone(), arg);
+        applications(ctx, app, &pi.res_type.val_of(arg_val), goal, depth, found)?;
+    }
+    Ok(())
+}
+
+/// The terms of type `tv` that are built by one of its introduction forms
+fn introductions(ctx: &Ctx, tv: &Value, depth: usize) -> Result<Vec<Core>> {
+    if tv.try_as::<Trivial>().is_some() {
+        return Ok(vec![cores::sole()]);
+    }
+
+    if let Some(Equal { typ, from, to }) = tv.try_as::<Equal<Value>>() {
+        if convert(ctx, typ, from, to).is_err() {
+            return Ok(vec![]);
+        }
+        return Ok(vec![cores::same(read_back(ctx, typ, from)?)]);
+    }
+
+    if tv.try_as::<Nat>().is_some() {
+        return Ok(vec![cores::zero()]);
+    }
+
+    // every atom is as good as any other
+    if tv.try_as::<Atom>().is_some() {
+        return Ok(vec![cores::quote("a")]);
+    }
+
+    if tv.try_as::<List<Value>>().is_some() {
+        return Ok(vec![cores::nil()]);
+    }
+
+    if let Some(Vector(_, len)) = tv.try_as::<Vector<Value>>() {
+        if is_zero(len) {
+            return Ok(vec![cores::vecnil()]);
+        }
+    }
+
+    if depth == 0 {
+        return Ok(vec![]);
+    }
+
+    let mut found = vec![];
+    if let Some(sigma) = tv.try_as::<Sigma<Value, Closure>>() {
+        let cars = if depends_on_arg(ctx, &sigma.arg_name, &sigma.car_type, &sigma.cdr_type)? {
+            proofs(ctx, &sigma.car_type, depth - 1)?
+        } else {
+            first_proof(ctx, &sigma.car_type, depth - 1)?
+                .into_iter()
+                .collect()
+        };
+        for car in cars {
+            let car_val = val_in_ctx(ctx, &car);
+            if let Some(cdr) = first_proof(ctx, &sigma.cdr_type.val_of(car_val), depth - 1)? {
+                found.push(cores::cons(car.clone(), cdr));
+            }
+        }
+    } else if let Some(Either(l, r)) = tv.try_as::<Either<Value>>() {
+        found.extend(first_proof(ctx, l, depth - 1)?.into_iter().map(cores::left));
+        found.extend(
+            first_proof(ctx, r, depth - 1)?
+                .into_iter()
+                .map(cores::right),
+        );
+    } else if let Some(Vector(e, len)) = tv.try_as::<Vector<Value>>() {
+        if let Some(len_1) = predecessor(len) {
+            let rest = values::vec(e.clone(), len_1);
+            if let (Some(head), Some(tail)) = (
+                first_proof(ctx, e, depth - 1)?,
+                first_proof(ctx, &rest, depth - 1)?,
+            ) {
+                found.push(cores::vec_cons(head, tail));
+            }
+        }
+    }
+    Ok(found)
+}
+
+/// The shallowest term of type `tv` that is nested no deeper than `depth`, if there is one
+fn first_proof(ctx: &Ctx, tv: &Value, depth: usize) -> Result<Option<Core>> {
+    match search(ctx, tv, depth) {
+        Ok(proof) => Ok(Some(proof)),
+        Err(Error::NoProofFound(_)) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Whether the type `c`, which may refer to a variable `x` of type `xt`, actually does
+fn depends_on_arg(ctx: &Ctx, x: &Symbol, xt: &Value, c: &Closure) -> Result<bool> {
+    let x_hat = ctx.fresh(x);
+    let ctx_hat = ctx.bind_free(x_hat.clone(), xt.clone())?;
+    let x_val = values::neutral(xt.clone(), NeutralVar(x_hat.clone()));
+    let t = c.val_of(x_val).read_back_type(&ctx_hat)?;
+    Ok(t.occurring_names().contains(&x_hat))
+}
+
+/// Whether applying a function of type `et` to enough arguments could result in a `goal`,
+/// judging by whether the types are built by the same type former
+fn may_return(ctx: &Ctx, et: &Value, goal: &Value) -> Result<bool> {
+    match et.try_as::<Pi<Value, Closure>>() {
+        Some(pi) => {
+            let x_hat = ctx.fresh(&pi.arg_name);
+            let ctx_hat = ctx.bind_free(x_hat.clone(), pi.arg_type.clone())?;
+            let x_val = values::neutral(pi.arg_type.clone(), NeutralVar(x_hat));
+            may_return(&ctx_hat, &pi.res_type.val_of(x_val), goal)
+        }
+        // the type is a variable, which may stand for any type
+        None if et.as_neutral().is_some() => Ok(true),
+        None => Ok(et.as_any().type_id() == goal.as_any().type_id()),
+    }
+}
@@ -0,0 +1,302 @@
+use crate::basics::{
+    Closure, Core, CoreInterface, Ctx, Env, NeutralInterface, Renaming, Value, ValueInterface, N,
+};
+use crate::errors::{Error, Result};
+use crate::normalize::{read_back, val_in_ctx};
+use crate::symbol::Symbol;
+use crate::types::functions::do_ap;
+use crate::types::pairs::{do_car, do_cdr};
+use crate::types::values::later;
+use crate::types::{cores, synth_type_level, values, MaybeTyped};
+use std::any::Any;
+use std::collections::HashSet;
+use std::fmt::Formatter;
+
+/// The type of infinite streams
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stream<T>(pub T);
+
+/// `(corec-Stream seed step)`: the stream that `step` unfolds from `seed`. The step turns a
+/// state into the stream's head and the next state. Once elaborated, the seed is annotated
+/// with the state type.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CorecStream {
+    seed: MaybeTyped,
+    step: Core,
+}
+
+/// A stream as a value: a state of type `state_type` and the step that unfolds it.
+/// Observations unfold it one step at a time, so it is never unfolded as a whole.
+#[derive(Debug)]
+pub struct StreamGenerator {
+    pub state_type: Value,
+    pub seed: Value,
+    pub step: Value,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StreamHead(pub Core);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StreamTail(pub Core);
+
+#[derive(Debug)]
+pub struct NeutralStreamHead(pub N);
+
+#[derive(Debug)]
+pub struct NeutralStreamTail(pub N);
+
+impl CorecStream {
+    pub fn new(seed: Core, step: Core) -> Self {
+        CorecStream {
+            seed: MaybeTyped::Plain(seed),
+            step,
+        }
+    }
+
+    pub fn typed(state_type: Core, seed: Core, step: Core) -> Self {
+        CorecStream {
+            seed: MaybeTyped::The(state_type, seed),
+            step,
+        }
+    }
+}
+
+impl CoreInterface for Stream<Core> {
+    impl_core_defaults!(
+        (0),
+        as_any,
+        same,
+        occurring_names,
+        alpha_equiv,
+        check_by_synth,
+        (resugar: stream)
+    );
+
+    fn val_of(&self, env: &Env) -> Value {
+        values::stream(later(env.clone(), self.0.clone()))
+    }
+
+    fn is_type(&self, ctx: &Ctx, r: &Renaming) -> Result<Core> {
+        Ok(cores::stream(self.0.is_type(ctx, r)?))
+    }
+
+    fn synth(&self, ctx: &Ctx, r: &Renaming) -> Result<(Core, Core)> {
+        let (e_out, level) = synth_type_level(ctx, r, &self.0)?;
+        Ok((cores::universe_level(level), cores::stream(e_out)))
+    }
+}
+
+impl CoreInterface for CorecStream {
+    impl_core_defaults!(
+        (seed, step),
+        as_any,
+        same,
+        occurring_names,
+        alpha_equiv,
+        no_type,
+        no_synth
+    );
+
+    fn val_of(&self, env: &Env) -> Value {
+        match &self.seed {
+            MaybeTyped::Plain(_) => unimplemented!("evaluate a desugared corec-Stream instead"),
+            MaybeTyped::The(st, seed) => Value::new(StreamGenerator {
+                state_type: later(env.clone(), st.clone()),
+                seed: later(env.clone(), seed.clone()),
+                step: later(env.clone(), self.step.clone()),
+            }),
+        }
+    }
+
+    fn check(&self, ctx: &Ctx, r: &Renaming, tv: &Value) -> Result<Core> {
+        let Stream(etv) = match tv.try_as::<Stream<Value>>() {
+            Some(stream) => stream,
+            None => return Err(Error::NotAStreamType(tv.read_back_type(ctx)?)),
+        };
+        let (st_out, seed_out) = match &self.seed {
+            MaybeTyped::Plain(seed) => seed.synth(ctx, r)?,
+            MaybeTyped::The(st, seed) => cores::the(st.clone(), seed.clone()).synth(ctx, r)?,
+        };
+        let stv = val_in_ctx(ctx, &st_out);
+        let step_out = self.step.check(ctx, r, &step_type(etv, &stv))?;
+        Ok(Core::new(CorecStream::typed(st_out, seed_out, step_out)))
+    }
+
+    fn resugar(&self) -> (HashSet<Symbol>, Core) {
+        let (seed_names, seed) = self.seed.resugar();
+        let (step_names, step) = self.step.resugar();
+        (
+            &seed_names | &step_names,
+            Core::new(CorecStream { seed, step }),
+        )
+    }
+}
+
+impl CoreInterface for StreamHead {
+    impl_core_defaults!(
+        (0),
+        as_any,
+        same,
+        occurring_names,
+        alpha_equiv,
+        no_type,
+        check_by_synth,
+        (resugar: stream_head)
+    );
+
+    fn val_of(&self, env: &Env) -> Value {
+        do_stream_head(&later(env.clone(), self.0.clone()))
+    }
+
+    fn synth(&self, ctx: &Ctx, r: &Renaming) -> Result<(Core, Core)> {
+        let (s_t, s_out) = self.0.synth(ctx, r)?;
+        match val_in_ctx(ctx, &s_t).try_as::<Stream<Value>>() {
+            Some(Stream(etv)) => Ok((etv.read_back_type(ctx)?, cores::stream_head(s_out))),
+            None => Err(Error::NotAStreamType(s_t)),
+        }
+    }
+}
+
+impl CoreInterface for StreamTail {
+    impl_core_defaults!(
+        (0),
+        as_any,
+        same,
+        occurring_names,
+        alpha_equiv,
+        no_type,
+        check_by_synth,
+        (resugar: stream_tail)
+    );
+
+    fn val_of(&self, env: &Env) -> Value {
+        do_stream_tail(&later(env.clone(), self.0.clone()))
+    }
+
+    fn synth(&self, ctx: &Ctx, r: &Renaming) -> Result<(Core, Core)> {
+        let (s_t, s_out) = self.0.synth(ctx, r)?;
+        match val_in_ctx(ctx, &s_t).try_as::<Stream<Value>>() {
+            Some(Stream(_)) => Ok((s_t, cores::stream_tail(s_out))),
+            None => Err(Error::NotAStreamType(s_t)),
+        }
+    }
+}
+
+impl_sexpr_display!(T: Stream<T>, ("Stream", 0));
+impl_sexpr_display!(StreamHead, ("stream-head", 0));
+impl_sexpr_display!(StreamTail, ("stream-tail", 0));
+
+impl std::fmt::Display for CorecStream {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.seed {
+            MaybeTyped::Plain(seed) => write!(f, "(corec-Stream {} {})", seed, self.step),
+            MaybeTyped::The(st, seed) => {
+                write!(f, "(corec-Stream (the {} {}) {})", st, seed, self.step)
+            }
+        }
+    }
+}
+
+impl ValueInterface for Stream<Value> {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn same(&self, other: &dyn ValueInterface) -> bool {
+        if let Some(other) = other.try_as::<Self>() {
+            self == other
+        } else {
+            false
+        }
+    }
+
+    fn read_back_type(&self, ctx: &Ctx) -> Result<Core> {
+        Ok(cores::stream(self.0.read_back_type(ctx)?))
+    }
+
+    /// Streams are read back as their generators rather than by unfolding them, so two
+    /// streams are the same when they are generated in the same way.
+    fn read_back(&self, ctx: &Ctx, tv: &Value, v: &Value) -> Result<Core> {
+        match v.try_as::<StreamGenerator>() {
+            Some(gen) => Ok(Core::new(CorecStream::typed(
+                gen.state_type.read_back_type(ctx)?,
+                read_back(ctx, &gen.state_type, &gen.seed)?,
+                read_back(ctx, &step_type(&self.0, &gen.state_type), &gen.step)?,
+            ))),
+            None => Err(Error::TypeMismatchVar(v.clone(), tv.clone())),
+        }
+    }
+}
+
+impl ValueInterface for StreamGenerator {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn same(&self, _other: &dyn ValueInterface) -> bool {
+        unimplemented!()
+    }
+
+    fn read_back_type(&self, _ctx: &Ctx) -> Result<Core> {
+        unimplemented!()
+    }
+}
+
+/// The type of a stream's step: `(Π ((s S)) (Pair E S))`
+fn step_type(etv: &Value, stv: &Value) -> Value {
+    let etv = etv.clone();
+    let stv = stv.clone();
+    values::pi(
+        "s",
+        stv.clone(),
+        Closure::higher(move |_| {
+            let stv = stv.clone();
+            values::sigma("e", etv.clone(), Closure::higher(move |_| stv.clone()))
+        }),
+    )
+}
+
+fn do_stream_head(s: &Value) -> Value {
+    if let Some(gen) = s.try_as::<StreamGenerator>() {
+        return do_car(&do_ap(&gen.step, gen.seed.clone()));
+    }
+
+    if let Some((stream, ne)) = s.as_neutral() {
+        if let Some(Stream(etv)) = stream.try_as::<Stream<Value>>() {
+            return values::neutral(etv.clone(), NeutralStreamHead(ne.clone()));
+        }
+    }
+
+    unreachable!("{:?}", s)
+}
+
+fn do_stream_tail(s: &Value) -> Value {
+    if let Some(gen) = s.try_as::<StreamGenerator>() {
+        return Value::new(StreamGenerator {
+            state_type: gen.state_type.clone(),
+            seed: do_cdr(&do_ap(&gen.step, gen.seed.clone())),
+            step: gen.step.clone(),
+        });
+    }
+
+    if let Some((stream, ne)) = s.as_neutral() {
+        if stream.try_as::<Stream<Value>>().is_some() {
+            return values::neutral(stream.clone(), NeutralStreamTail(ne.clone()));
+        }
+    }
+
+    unreachable!("{:?}", s)
+}
+
+impl NeutralInterface for NeutralStreamHead {
+    fn read_back_neutral(&self, ctx: &Ctx) -> Result<Core> {
+        Ok(cores::stream_head(self.0.read_back_neutral(ctx)?))
+    }
+}
+
+impl NeutralInterface for NeutralStreamTail {
+    fn read_back_neutral(&self, ctx: &Ctx) -> Result<Core> {
+        Ok(cores::stream_tail(self.0.read_back_neutral(ctx)?))
+    }
+}
@@ -1,11 +1,14 @@
 use crate::alpha;
-use crate::basics::{Core, CoreInterface, Ctx, Env, NeutralInterface, Renaming, Value};
+use crate::alpha::alpha_equiv_aux;
+use crate::basics::{Core, CoreInterface, Ctx, Env, NeutralInterface, Renaming, Value, R};
 use crate::errors::Result;
 use crate::symbol::Symbol;
 use crate::types::values::later;
 use crate::types::{cores, values};
 use std::collections::HashSet;
 use std::fmt::Formatter;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct ToDo {
@@ -16,6 +19,87 @@ pub struct ToDo {
 #[derive(Debug)]
 pub struct NeutralTodo(Symbol, Value);
 
+/// What is left to do at a hole: the type of the expression that belongs there, and the local
+/// variables that are in scope for it, outermost first
+#[derive(Debug, Clone, PartialEq)]
+pub struct Goal {
+    pub name: Symbol,
+    pub goal: Core,
+    pub context: Vec<(Symbol, Core)>,
+}
+
+/// What becomes of the holes that are checked in a context
+#[derive(Debug, Clone, Default)]
+pub struct Holes {
+    /// Where the goals of the holes are collected, if they are
+    goals: Option<R<Mutex<Vec<Goal>>>>,
+    /// The hole that is being filled in, if there is one
+    fill: Option<R<Fill>>,
+}
+
+/// A hole, the expression that goes there, and whether the hole has been reached
+#[derive(Debug)]
+struct Fill(Symbol, Core, AtomicBool);
+
+impl Holes {
+    pub(crate) fn collecting() -> Self {
+        Holes {
+            goals: Some(R::new(Mutex::new(vec![]))),
+            fill: None,
+        }
+    }
+
+    /// The same holes, except that `e` is checked wherever the hole `hole` is
+    pub(crate) fn filling(&self, hole: Symbol, e: Core) -> Self {
+        Holes {
+            goals: self.goals.clone(),
+            fill: Some(R::new(Fill(hole, e, AtomicBool::new(false)))),
+        }
+    }
+
+    /// Whether the hole that is being filled in has been reached
+    pub(crate) fn filled(&self) -> bool {
+        self.fill
+            .as_ref()
+            .is_some_and(|fill| fill.2.load(Ordering::Relaxed))
+    }
+
+    /// The expression that fills in the hole `hole`, if it is being filled in
+    fn filler(&self, hole: &Symbol) -> Option<Core> {
+        match self.fill.as_deref() {
+            Some(Fill(x, e, filled)) if x == hole => {
+                filled.store(true, Ordering::Relaxed);
+                Some(e.clone())
+            }
+            _ => None,
+        }
+    }
+
+    /// The goals collected so far. A hole that is checked more than once is reported once,
+    /// with the goal it was checked against last.
+    pub(crate) fn goals(&self) -> Vec<Goal> {
+        match &self.goals {
+            Some(goals) => goals.lock().unwrap().clone(),
+            None => vec![],
+        }
+    }
+
+    fn report(&self, goal: Goal) {
+        if let Some(goals) = &self.goals {
+            let mut goals = goals.lock().unwrap();
+            goals.retain(|g| g.name != goal.name);
+            goals.push(goal);
+        }
+    }
+}
+
+/// Contexts are the same regardless of what becomes of their holes
+impl PartialEq for Holes {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
 impl ToDo {
     pub fn new(name: impl Into<Symbol>) -> Self {
         ToDo {
@@ -36,7 +120,10 @@ impl CoreInterface for ToDo {
     impl_core_defaults!((0, 1), as_any, same, no_synth);
 
     fn occurring_names(&self) -> HashSet<Symbol> {
-        todo!()
+        match &self.typ {
+            Some(typ) => typ.occurring_names(),
+            None => HashSet::new(),
+        }
     }
 
     fn val_of(&self, env: &Env) -> Value {
@@ -47,22 +134,35 @@ impl CoreInterface for ToDo {
         )
     }
 
-    fn check(&self, ctx: &Ctx, _r: &Renaming, tv: &Value) -> Result<Core> {
+    fn check(&self, ctx: &Ctx, r: &Renaming, tv: &Value) -> Result<Core> {
+        if let Some(e) = ctx.holes().filler(&self.name) {
+            return e.check(ctx, r, tv);
+        }
         let ty = tv.read_back_type(ctx)?;
-        let todo_out = ToDo::annotated(self.name.clone(), ty);
-        // super simple hacky way to report types of todos
-        eprintln!("{}", todo_out);
-        Ok(Core::new(todo_out))
+        ctx.holes().report(Goal {
+            name: self.name.clone(),
+            goal: ty.clone(),
+            context: ctx.local_variables()?,
+        });
+        Ok(cores::annotated_todo(self.name.clone(), ty))
     }
 
+    /// Holes are only the same as themselves, so they are told apart by their names
     fn alpha_equiv_aux(
         &self,
-        _other: &dyn CoreInterface,
-        _lvl: usize,
-        _b1: &alpha::Bindings,
-        _b2: &alpha::Bindings,
+        other: &dyn CoreInterface,
+        lvl: usize,
+        b1: &alpha::Bindings,
+        b2: &alpha::Bindings,
     ) -> bool {
-        todo!()
+        match other.try_as::<Self>() {
+            Some(other) if self.name == other.name => match (&self.typ, &other.typ) {
+                (Some(t1), Some(t2)) => alpha_equiv_aux(lvl, b1, b2, t1, t2),
+                (None, None) => true,
+                _ => false,
+            },
+            _ => false,
+        }
     }
 
     fn resugar(&self) -> (HashSet<Symbol>, Core) {
@@ -75,12 +175,20 @@ impl CoreInterface for ToDo {
 
 impl std::fmt::Display for ToDo {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "(TODO {}: {})",
-            self.name.name(),
-            self.typ.as_ref().unwrap()
-        )
+        match &self.typ {
+            Some(typ) => write!(f, "(TODO {}: {})", self.name.name(), typ),
+            None => write!(f, "(TODO {})", self.name.name()),
+        }
+    }
+}
+
+impl std::fmt::Display for Goal {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (x, t) in &self.context {
+            writeln!(f, "{} : {}", x.name(), t)?;
+        }
+        writeln!(f, "----------------")?;
+        write!(f, "{} : {}", self.name.name(), self.goal)
     }
 }
 
@@ -1,11 +1,14 @@
 use crate::basics::{Core, CoreInterface, Ctx, Env, Renaming, Value, ValueInterface};
-use crate::errors::{Error, Result};
+use crate::errors::Result;
+use crate::symbol::Symbol;
 use crate::types::{cores, values};
 use std::any::Any;
+use std::collections::HashSet;
+use std::fmt::Formatter;
 
-/// The type of all types
-#[derive(Debug, Copy, Clone)]
-pub struct Universe;
+/// The type of all types up to a given level; `U` is the lowest level
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Universe(pub usize);
 
 impl ValueInterface for Universe {
     fn as_any(&self) -> &dyn Any {
@@ -13,11 +16,11 @@ impl ValueInterface for Universe {
     }
 
     fn same(&self, other: &dyn ValueInterface) -> bool {
-        other.as_any().is::<Self>()
+        other.try_as::<Self>() == Some(self)
     }
 
     fn read_back_type(&self, _ctx: &Ctx) -> Result<Core> {
-        Ok(cores::universe())
+        Ok(cores::universe_level(self.0))
     }
 
     fn read_back(&self, ctx: &Ctx, _tv: &Value, v: &Value) -> Result<Core> {
@@ -26,24 +29,39 @@ impl ValueInterface for Universe {
 }
 
 impl CoreInterface for Universe {
-    impl_core_defaults!(
-        _,
-        as_any,
-        same,
-        occurring_names,
-        alpha_equiv,
-        simple_type,
-        check_by_synth,
-        (resugar: universe)
-    );
+    impl_core_defaults!((0), as_any, same);
+    impl_core_defaults!(_, occurring_names, alpha_equiv, simple_type, check_by_synth);
 
     fn val_of(&self, _env: &Env) -> Value {
-        values::universe()
+        values::universe_level(self.0)
     }
 
     fn synth(&self, _ctx: &Ctx, _r: &Renaming) -> Result<(Core, Core)> {
-        Err(Error::UhasNoType)
+        Ok((
+            cores::universe_level(self.0 + 1),
+            cores::universe_level(self.0),
+        ))
+    }
+
+    fn resugar(&self) -> (HashSet<Symbol>, Core) {
+        (HashSet::new(), cores::universe_level(self.0))
     }
 }
 
-impl_sexpr_display!(Universe, "U");
+impl std::fmt::Display for Universe {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            0 => write!(f, "U"),
+            level => write!(f, "U{}", level),
+        }
+    }
+}
+
+/// Parse the level of a universe name: `U` is level 0, `U1` level 1, and so on
+pub fn universe_level_of(name: &str) -> Option<usize> {
+    match name.strip_prefix('U')? {
+        "" => Some(0),
+        digits if digits.chars().all(|c| c.is_ascii_digit()) => digits.parse().ok(),
+        _ => None,
+    }
+}
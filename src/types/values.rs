@@ -5,22 +5,29 @@ use crate::types::atom::{Atom, Quote};
 use crate::types::delay::Delay;
 use crate::types::either::{Either, Left, Right};
 use crate::types::equality::{Equal, Same};
-use crate::types::functions::{Lambda, Pi};
+use crate::types::functions::{ImplicitPi, Lambda, Pi};
+use crate::types::integer::{Int, IntLiteral};
 use crate::types::lists::{List, ListCons, Nil};
 use crate::types::natural::Add1;
-use crate::types::natural::{Nat, Zero};
+use crate::types::natural::{Nat, Numeral, Zero};
 pub use crate::types::neutral::neutral;
 use crate::types::pairs::{Cons, Sigma};
+use crate::types::stream::Stream;
 use crate::types::trivial::{Sole, Trivial};
 use crate::types::universe::Universe;
 use crate::types::vec::{VecNil, Vector, VectorCons};
+use num_bigint::{BigInt, BigUint};
 
 pub fn later(env: Env, exp: Core) -> Value {
     Value::new(Delay::new(env, exp))
 }
 
 pub fn universe() -> Value {
-    Value::new(Universe)
+    universe_level(0)
+}
+
+pub fn universe_level(level: usize) -> Value {
+    Value::new(Universe(level))
 }
 
 pub fn nat() -> Value {
@@ -35,12 +42,16 @@ pub fn add1(n: Value) -> Value {
     Value::new(Add1(n))
 }
 
-pub fn the_nat(n: u64) -> Value {
-    let mut out = zero();
-    for _ in 0..n {
-        out = add1(out);
-    }
-    out
+pub fn numeral(n: BigUint) -> Value {
+    Value::new(Numeral(n))
+}
+
+pub fn int() -> Value {
+    Value::new(Int)
+}
+
+pub fn int_literal(i: BigInt) -> Value {
+    Value::new(IntLiteral(i))
 }
 
 pub fn pi(x: impl Into<Symbol>, arg_type: Value, res_type: Closure) -> Value {
@@ -51,6 +62,14 @@ pub fn pi(x: impl Into<Symbol>, arg_type: Value, res_type: Closure) -> Value {
     })
 }
 
+pub fn implicit_pi(x: impl Into<Symbol>, arg_type: Value, res_type: Closure) -> Value {
+    Value::new(ImplicitPi {
+        arg_name: x.into(),
+        arg_type,
+        res_type,
+    })
+}
+
 pub fn lambda(arg_name: Symbol, body: Closure) -> Value {
     Value::new(Lambda { arg_name, body })
 }
@@ -119,6 +138,10 @@ pub fn right(r: Value) -> Value {
     Value::new(Right(r))
 }
 
+pub fn stream(t: Value) -> Value {
+    Value::new(Stream(t))
+}
+
 pub fn trivial() -> Value {
     Value::new(Trivial)
 }
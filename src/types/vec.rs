@@ -6,9 +6,9 @@ use crate::normalize::{read_back, val_in_ctx};
 use crate::symbol::Symbol;
 use crate::typechecker::convert;
 use crate::types::functions::do_ap;
-use crate::types::natural::{Add1, Nat, Zero};
+use crate::types::natural::{is_zero, predecessor, Nat};
 use crate::types::values::later;
-use crate::types::{cores, values};
+use crate::types::{cores, synth_type_level, values};
 use std::any::Any;
 use std::collections::HashSet;
 
@@ -79,9 +79,9 @@ impl CoreInterface for Vector<Core> {
     }
 
     fn synth(&self, ctx: &Ctx, r: &Renaming) -> Result<(Core, Core)> {
-        let e_out = self.0.check(ctx, r, &values::universe())?;
+        let (e_out, level) = synth_type_level(ctx, r, &self.0)?;
         let len_out = self.1.check(ctx, r, &values::nat())?;
-        Ok((cores::universe(), cores::vec(e_out, len_out)))
+        Ok((cores::universe_level(level), cores::vec(e_out, len_out)))
     }
 }
 
@@ -106,7 +106,7 @@ impl CoreInterface for VecNil {
 
     fn check(&self, ctx: &Ctx, _r: &Renaming, tv: &Value) -> Result<Core> {
         if let Some(Vector(_, n)) = tv.try_as::<Vector<Value>>() {
-            if n.try_as::<Zero>().is_some() {
+            if is_zero(n) {
                 Ok(cores::vecnil())
             } else {
                 Err(Error::LengthNotZero(values::nat().read_back(ctx, n)?))
@@ -204,7 +204,7 @@ impl CoreInterface for Tail {
         Ok((
             cores::vec(
                 etv.read_back_type(ctx)?,
-                read_back(ctx, &values::nat(), len_minus_1)?,
+                read_back(ctx, &values::nat(), &len_minus_1)?,
             ),
             cores::tail(es_out),
         ))
@@ -300,9 +300,9 @@ fn ind_vec_step_type(ev: Value, mot_v: Value) -> Value {
     })
 }
 
-fn expect_non_empty_vec<'a>(ctx: &Ctx, tv: &'a Value) -> Result<(&'a Value, &'a Value)> {
+fn expect_non_empty_vec<'a>(ctx: &Ctx, tv: &'a Value) -> Result<(&'a Value, Value)> {
     if let Some(Vector(etv, len)) = tv.try_as::<Vector<Value>>() {
-        if let Some(Add1(len_minus_one)) = len.try_as::<Add1<Value>>() {
+        if let Some(len_minus_one) = predecessor(len) {
             Ok((etv, len_minus_one))
         } else {
             Err(Error::LengthZero(values::nat().read_back(ctx, len)?))
@@ -366,11 +366,11 @@ impl ValueInterface for Vector<Value> {
     }
 
     fn read_back(&self, ctx: &Ctx, tv: &Value, v: &Value) -> Result<Core> {
-        if self.1.try_as::<Zero>().is_some() && v.try_as::<VecNil>().is_some() {
+        if is_zero(&self.1) && v.try_as::<VecNil>().is_some() {
             return Ok(cores::vecnil());
         }
 
-        if let Some(Add1(len_minus_one_v)) = self.1.try_as::<Add1<Value>>() {
+        if let Some(len_minus_one_v) = predecessor(&self.1) {
             if let Some(VectorCons(h, t)) = v.try_as::<VectorCons<Value>>() {
                 return Ok(cores::vec_cons(
                     read_back(ctx, &self.0, h)?,
@@ -429,8 +429,8 @@ fn do_head(tgt_v: &Value) -> Value {
 
     match tgt_v.as_neutral() {
         Some((vec, ne)) => match vec.try_as::<Vector<Value>>() {
-            Some(Vector(ev, lenv)) => match lenv.try_as::<Add1<Value>>() {
-                Some(Add1(_)) => return values::neutral(ev.clone(), NeutralHead(ne.clone())),
+            Some(Vector(ev, lenv)) => match predecessor(lenv) {
+                Some(_) => return values::neutral(ev.clone(), NeutralHead(ne.clone())),
                 None => {}
             },
             None => {}
@@ -448,10 +448,10 @@ fn do_tail(tgt_v: &Value) -> Value {
 
     match tgt_v.as_neutral() {
         Some((vec, ne)) => match vec.try_as::<Vector<Value>>() {
-            Some(Vector(ev, lenv)) => match lenv.try_as::<Add1<Value>>() {
-                Some(Add1(len_minus_1v)) => {
+            Some(Vector(ev, lenv)) => match predecessor(lenv) {
+                Some(len_minus_1v) => {
                     return values::neutral(
-                        values::vec(ev.clone(), len_minus_1v.clone()),
+                        values::vec(ev.clone(), len_minus_1v),
                         NeutralTail(ne.clone()),
                     )
                 }
@@ -482,20 +482,19 @@ fn do_ind_vec(len_v: Value, vec_v: Value, mot_v: Value, b_v: Value, s_v: Value)
 }
 
 fn _do_ind_vec(len_v: &Value, vec_v: &Value, mot_v: Value, b_v: Value, s_v: &Value) -> Value {
-    if let (Some(_), Some(_)) = (len_v.try_as::<Zero>(), vec_v.try_as::<VecNil>()) {
+    if is_zero(len_v) && vec_v.try_as::<VecNil>().is_some() {
         return b_v;
     }
 
-    if let (Some(Add1(len_m1_v)), Some(VectorCons(h, t))) = (
-        len_v.try_as::<Add1<Value>>(),
-        vec_v.try_as::<VectorCons<Value>>(),
-    ) {
+    if let (Some(len_m1_v), Some(VectorCons(h, t))) =
+        (predecessor(len_v), vec_v.try_as::<VectorCons<Value>>())
+    {
         return do_ap(
             &do_ap(
                 &do_ap(&do_ap(s_v, len_m1_v.clone()), h.clone()),
                 do_tail(vec_v),
             ),
-            _do_ind_vec(len_m1_v, t, mot_v, b_v, s_v),
+            _do_ind_vec(&len_m1_v, t, mot_v, b_v, s_v),
         );
     }
 
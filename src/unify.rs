@@ -0,0 +1,312 @@
+//! Metavariables for implicit arguments.
+//!
+//! Elaborating an application creates a metavariable for each implicit argument. They are
+//! solved by unification, which piggybacks on alpha-equivalence: comparing an unsolved
+//! metavariable with an expression makes that expression its solution. Thus the usual
+//! `typechecker::same_type` and `typechecker::convert` solve any metavariables they meet.
+//! A solution must have the metavariable's type, and is undone if the rest of the comparison
+//! fails.
+
+use crate::alpha::Bindings;
+use crate::basics::{Closure, Core, CoreInterface, Ctx, Mutex, Renaming, Value, R};
+use crate::errors::{Error, Result};
+use crate::normalize::val_in_ctx;
+use crate::symbol::Symbol;
+use crate::types::functions::ImplicitPi;
+use crate::types::meta::{Meta, NeutralMeta};
+use crate::types::{cores, values};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// An unknown expression of type `tv`, named after the implicit binder it stands for
+#[derive(Debug)]
+pub struct MetaVar {
+    pub name: Symbol,
+    /// A name unique to this metavariable, for the occurs check
+    pub key: Symbol,
+    /// The context the metavariable was created in, which its solution must be checked in
+    ctx: Ctx,
+    tv: Value,
+    solution: Mutex<Option<Core>>,
+}
+
+impl MetaVar {
+    pub fn solution(&self) -> Option<Core> {
+        self.solution.lock().unwrap().clone()
+    }
+
+    pub fn as_value(self: &R<Self>) -> Value {
+        values::neutral(self.tv.clone(), NeutralMeta(self.clone()))
+    }
+
+    /// Try to solve the metavariable with `e`. The solution must not refer to itself, or to
+    /// variables bound in `e`'s surroundings (by `bound`), as those are not in scope where
+    /// the metavariable was created. It must also have the metavariable's type, so a type
+    /// must be in the right universe.
+    pub fn solve(self: &R<Self>, e: &Core, bound: &Bindings) -> bool {
+        let names = e.occurring_names();
+        if names.contains(&self.key) || names.iter().any(|x| bound.assv(x).is_some()) {
+            return false;
+        }
+        if !self.has_type(e) {
+            return false;
+        }
+        *self.solution.lock().unwrap() = Some(e.clone());
+        if let Some(trail) = bound.trail() {
+            trail.borrow_mut().push(self.clone());
+        }
+        true
+    }
+
+    /// Forget the solution, when the comparison that found it fails
+    pub fn unsolve(&self) {
+        *self.solution.lock().unwrap() = None;
+    }
+
+    /// Whether `e` has the metavariable's type. While that type depends on metavariables
+    /// that are unsolved, checking against it would solve them too, so the check waits
+    /// until the solution is used.
+    fn has_type(&self, e: &Core) -> bool {
+        match self.tv.read_back_type(&self.ctx) {
+            Ok(t) if !t.occurring_names().iter().any(is_meta_key) => {}
+            _ => return true,
+        }
+        match e.check(&self.ctx, &Renaming::new(), &self.tv) {
+            Ok(_) | Err(Error::UnsolvedMeta(..)) => true,
+            Err(_) => false,
+        }
+    }
+}
+
+fn is_meta_key(x: &Symbol) -> bool {
+    x.name().starts_with('?')
+}
+
+/// The metavariables created while elaborating one application
+#[derive(Debug, Default)]
+pub struct Metas(Vec<R<MetaVar>>);
+
+impl Metas {
+    pub fn new() -> Self {
+        Metas(vec![])
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn fresh(&mut self, ctx: &Ctx, name: &Symbol, tv: Value) -> R<MetaVar> {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+        let id = COUNTER.fetch_add(1, Ordering::Relaxed);
+        let meta = R::new(MetaVar {
+            name: name.clone(),
+            key: Symbol::new(&format!("?{}#{}", name.name(), id)),
+            ctx: ctx.clone(),
+            tv,
+            solution: Mutex::new(None),
+        });
+        self.0.push(meta.clone());
+        meta
+    }
+
+    /// Apply `e`, of type `tv`, to a new metavariable for each leading implicit argument
+    pub fn instantiate(&mut self, ctx: &Ctx, tv: Value, e: Core) -> (Value, Core) {
+        let mut tv = tv;
+        let mut e = e;
+        while let Some(pi) = tv.try_as::<ImplicitPi<Value, Closure>>() {
+            let meta = self.fresh(ctx, &pi.arg_name, pi.arg_type.clone());
+            e = cores::implicit_app(e, Core::new(Meta(meta.clone())));
+            tv = pi.res_type.val_of(meta.as_value());
+        }
+        (tv, e)
+    }
+
+    pub fn has_unsolved(&self) -> bool {
+        self.0.iter().any(|m| m.solution().is_none())
+    }
+
+    /// Replace the solved metavariables in the type `tv` by their solutions
+    pub fn zonk(&self, ctx: &Ctx, tv: &Value) -> Result<Value> {
+        if self.is_empty() {
+            Ok(tv.clone())
+        } else {
+            Ok(val_in_ctx(ctx, &tv.read_back_type(ctx)?))
+        }
+    }
+
+    /// Fail unless every metavariable was solved while elaborating `e`
+    pub fn check_solved(&self, e: &Core) -> Result<()> {
+        match self.0.iter().find(|m| m.solution().is_none()) {
+            Some(m) => Err(Error::UnsolvedMeta(m.name.clone(), e.clone())),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Metas;
+    use crate::alpha::is_alpha_equiv;
+    use crate::basics::{Core, CoreInterface, Ctx, Renaming, Value};
+    use crate::errors::Error;
+    use crate::normalize::val_in_ctx;
+    use crate::rep::{eval_normalize, norm};
+    use crate::types::functions::ImplicitPi;
+    use crate::types::meta::Meta;
+    use crate::types::{cores, values};
+
+    fn define(ctx: &mut Ctx, name: &str, t: &str, v: &str) -> Result<(), String> {
+        eval_normalize(ctx, &format!("(claim {} {})", name, t))?;
+        eval_normalize(ctx, &format!("(define {} {})", name, v))?;
+        Ok(())
+    }
+
+    fn evaluates_to(ctx: &Ctx, e: &str, t: &str, v: &str) {
+        assert_eq!(
+            norm(ctx, &e.parse().unwrap()),
+            Ok(Core::the(t.parse().unwrap(), v.parse().unwrap()))
+        );
+    }
+
+    fn with_lists() -> Ctx {
+        let mut ctx = Ctx::new();
+        define(
+            &mut ctx,
+            "length",
+            "(∀ ((E U)) (-> (List E) Nat))",
+            "(λ (es) (rec-List es 0 (λ (e es n) (add1 n))))",
+        )
+        .unwrap();
+        define(
+            &mut ctx,
+            "append",
+            "(∀ ((E U)) (-> (List E) (List E) (List E)))",
+            "(λ (start end) (rec-List start end (λ (e es rest) (:: e rest))))",
+        )
+        .unwrap();
+        ctx
+    }
+
+    #[test]
+    fn implicit_arguments_are_found_from_the_explicit_arguments() {
+        let ctx = with_lists();
+        evaluates_to(
+            &ctx,
+            "(length (append (:: 'a nil) (:: 'b nil)))",
+            "Nat",
+            "2",
+        );
+        evaluates_to(
+            &ctx,
+            "(append (:: 1 nil) (:: 2 nil))",
+            "(List Nat)",
+            "(:: 1 (:: 2 nil))",
+        );
+        assert_eq!(
+            norm(&ctx, &"(append (:: 'a nil) (:: 1 nil))".parse().unwrap()),
+            Err(Error::WrongType(
+                "Nat".parse().unwrap(),
+                "Atom".parse().unwrap()
+            ))
+        );
+    }
+
+    #[test]
+    fn implicit_arguments_are_found_from_the_expected_type() {
+        let mut ctx = with_lists();
+        define(&mut ctx, "empty", "(∀ ((E U)) (List E))", "nil").unwrap();
+        evaluates_to(&ctx, "(the (List Atom) empty)", "(List Atom)", "nil");
+        evaluates_to(
+            &ctx,
+            "(append (:: 'a nil) empty)",
+            "(List Atom)",
+            "(:: 'a nil)",
+        );
+        evaluates_to(
+            &ctx,
+            "((the (-> (List Nat) Nat) length) (:: 1 nil))",
+            "Nat",
+            "1",
+        );
+    }
+
+    #[test]
+    fn implicit_binders_are_in_scope_in_definitions() {
+        let mut ctx = with_lists();
+        define(
+            &mut ctx,
+            "reverse",
+            "(∀ ((E U)) (-> (List E) (List E)))",
+            "(λ (es) (rec-List es (the (List E) nil) (λ (e es r) (append r (:: e nil)))))",
+        )
+        .unwrap();
+        evaluates_to(
+            &ctx,
+            "(reverse (:: 1 (:: 2 nil)))",
+            "(List Nat)",
+            "(:: 2 (:: 1 nil))",
+        );
+        evaluates_to(&ctx, "((@ reverse Atom) nil)", "(List Atom)", "nil");
+    }
+
+    #[test]
+    fn unsolved_metavariables_are_reported() {
+        let mut ctx = with_lists();
+        define(&mut ctx, "k", "(∀ ((A U)) Nat)", "0").unwrap();
+        assert_eq!(
+            norm(&ctx, &"(the Nat k)".parse().unwrap()),
+            Err(Error::UnsolvedMeta("A".into(), "k".parse().unwrap()))
+        );
+        assert_eq!(
+            norm(&ctx, &"(length nil)".parse().unwrap()),
+            Err(Error::UnsolvedMeta(
+                "E".into(),
+                "(length nil)".parse().unwrap()
+            ))
+        );
+    }
+
+    #[test]
+    fn solutions_must_have_the_type_of_the_metavariable() {
+        let mut ctx = with_lists();
+        define(&mut ctx, "id", "(∀ ((A U)) (-> A A))", "(λ (a) a)").unwrap();
+        assert!(norm(&ctx, &"(length (:: U nil))".parse().unwrap()).is_err());
+        assert!(norm(&ctx, &"(id U)".parse().unwrap()).is_err());
+        evaluates_to(&ctx, "(id 'a)", "Atom", "'a");
+    }
+
+    #[test]
+    fn solutions_are_undone_when_the_comparison_fails() {
+        let mut metas = Metas::new();
+        let a = metas.fresh(&Ctx::new(), &"A".into(), values::universe());
+        let pair = |car, cdr| cores::sigma("x", car, cdr);
+        let with_meta = pair(Core::new(Meta(a.clone())), cores::nat());
+
+        assert!(!is_alpha_equiv(
+            &with_meta,
+            &pair(cores::atom(), cores::atom())
+        ));
+        assert_eq!(a.solution(), None);
+
+        assert!(is_alpha_equiv(
+            &with_meta,
+            &pair(cores::atom(), cores::nat())
+        ));
+        assert_eq!(a.solution(), Some(cores::atom()));
+    }
+
+    #[test]
+    fn implicit_pi_types_are_compared_by_their_read_backs() {
+        let ctx = Ctx::new();
+        let t = |s: &str| -> Value {
+            let e: Core = s.parse().unwrap();
+            val_in_ctx(&ctx, &e.is_type(&ctx, &Renaming::new()).unwrap())
+        };
+        let id_type = t("(∀ ((A U)) (-> A A))");
+        assert!(id_type
+            .try_as::<ImplicitPi<Value, super::Closure>>()
+            .is_some());
+        assert_eq!(id_type, t("(∀ ((B U)) (-> B B))"));
+        assert_ne!(id_type, t("(∀ ((A U)) (-> A Nat))"));
+        assert_ne!(id_type, t("(Π ((A U)) (-> A A))"));
+    }
+}